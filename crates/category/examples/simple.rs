@@ -15,26 +15,30 @@ fn main() {
     print_category(&category);
 
     // Construct identity morphisms for every object
-    category.apply_rule(
-        &axioms::rule_identity().unwrap(),
-        Bindings::<&str>::new(),
-        |_| (),
-        |_, _| (),
-        |_| (),
-    );
+    category
+        .apply_rule(
+            &axioms::rule_identity().unwrap(),
+            Bindings::<&str>::new(),
+            |_| (),
+            |_, _| (),
+            |_| (),
+        )
+        .unwrap();
 
     print_category(&category);
 
     // Compose our morphism f with the identity morphism
     let mut bindings = Bindings::new();
     bindings.bind_morphism("f", morphism_f); // "f" is from the rule
-    category.apply_rule(
-        &axioms::rule_composition().unwrap(),
-        bindings,
-        |_| (),
-        |_, _| (),
-        |_| (),
-    );
+    category
+        .apply_rule(
+            &axioms::rule_composition().unwrap(),
+            bindings,
+            |_| (),
+            |_, _| (),
+            |_| (),
+        )
+        .unwrap();
 
     print_category(&category);
 }
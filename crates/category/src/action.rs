@@ -1,6 +1,6 @@
 use super::*;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Action<O, M, E> {
     NewObjects(Vec<(Option<ObjectId>, Object<O>)>),
     ExtendObjectTags(Vec<(ObjectId, Vec<ObjectTag>)>),
@@ -12,11 +12,37 @@ pub enum Action<O, M, E> {
     RemoveMorphisms(Vec<MorphismId>),
     NewEqualities(Vec<(Equality, E)>),
     RemoveEqualities(Vec<Equality>),
+    /// Records what a rule application has added.
+    NewDerivation(Derivation),
+    /// Forgets the derivation recorded at the step.
+    RemoveDerivation(usize),
 }
 
 impl<O, M, E> Category<O, M, E> {
+    /// Perform the undo actions in order, discarding the actions to redo them.
+    pub(crate) fn action_undo(&mut self, actions: Vec<Action<O, M, E>>) {
+        for action in actions {
+            self.action_do_unchecked(action);
+        }
+    }
+
     /// Perform the action and returns the inverse action that can be used to undo the action.
     pub fn action_do(&mut self, action_do: Action<O, M, E>) -> Vec<Action<O, M, E>> {
+        // Only an action that breaks a valid category is a bug
+        let valid = cfg!(debug_assertions) && self.validate().is_ok();
+        let undo = self.action_do_unchecked(action_do);
+        if valid {
+            self.debug_validate();
+        }
+        undo
+    }
+
+    /// Perform the action without validating the result,
+    /// as the category may be invalid in the middle of a rule application.
+    pub(crate) fn action_do_unchecked(
+        &mut self,
+        action_do: Action<O, M, E>,
+    ) -> Vec<Action<O, M, E>> {
         match action_do {
             Action::NewObjects(objects) => {
                 let objects = objects
@@ -137,31 +163,33 @@ impl<O, M, E> Category<O, M, E> {
                 }
             }
             Action::RemoveObjects(objects) => {
-                let (objects, morphisms) = objects
+                let objects: Vec<_> = objects
                     .into_iter()
-                    .filter_map(|id| {
-                        self.remove_object(id)
-                            .map(|(object, morphisms)| (id, object, morphisms))
-                    })
-                    .map(|(object_id, object, morphisms)| {
-                        let morphisms: Vec<_> = morphisms
-                            .into_iter()
-                            .map(|(id, morphism)| (Some(id), morphism))
-                            .collect();
-                        ((object_id, object), morphisms)
-                    })
-                    .fold(
-                        (Vec::new(), Vec::new()),
-                        |(mut acc_objects, mut acc_morphisms), ((object_id, object), morphisms)| {
-                            acc_objects.push((Some(object_id), object));
-                            acc_morphisms.extend(morphisms);
-                            (acc_objects, acc_morphisms)
-                        },
-                    );
-                let mut undo = vec![Action::NewObjects(objects)];
-                if !morphisms.is_empty() {
-                    undo.push(Action::NewMorphisms(morphisms));
+                    .filter(|id| self.objects.contains(id))
+                    .collect();
+
+                // Remove the connected morphisms first, so that their equalities
+                // and the tags referring to them are removed as well
+                let mut morphisms: Vec<_> = objects
+                    .iter()
+                    .flat_map(|&id| self.morphisms.connected(id))
+                    .collect();
+                morphisms.sort();
+                morphisms.dedup();
+                let morphisms_undo = if morphisms.is_empty() {
+                    vec![]
+                } else {
+                    self.action_do_unchecked(Action::RemoveMorphisms(morphisms))
                 };
+
+                let removed: Vec<_> = objects
+                    .iter()
+                    .filter_map(|&id| self.remove_object(id).map(|(object, _)| (Some(id), object)))
+                    .collect();
+
+                let mut undo = vec![Action::NewObjects(removed)];
+                undo.extend(morphisms_undo);
+                undo.extend(self.remove_dangling_tags(&objects, &[]));
                 undo
             }
             Action::RemoveMorphisms(morphisms) => {
@@ -191,10 +219,12 @@ impl<O, M, E> Category<O, M, E> {
                     })
                     .collect();
 
+                let removed: Vec<_> = morphisms.iter().filter_map(|(id, _)| *id).collect();
                 let mut undo = vec![Action::NewMorphisms(morphisms)];
                 if !equalities.is_empty() {
                     undo.push(Action::NewEqualities(equalities));
                 }
+                undo.extend(self.remove_dangling_tags(&[], &removed));
                 undo
             }
             Action::NewEqualities(equalities) => {
@@ -229,6 +259,101 @@ impl<O, M, E> Category<O, M, E> {
                     .collect();
                 vec![Action::NewEqualities(equalities)]
             }
+            Action::NewDerivation(derivation) => {
+                vec![Action::RemoveDerivation(self.new_derivation(derivation))]
+            }
+            Action::RemoveDerivation(step) => match self.remove_derivation(step) {
+                Some(derivation) => vec![Action::NewDerivation(derivation)],
+                None => vec![],
+            },
+        }
+    }
+}
+
+impl<O, M, E> Category<O, M, E> {
+    /// Removes the tags referring to the removed objects or morphisms.
+    /// Returns the actions that restore the tags in their original order.
+    fn remove_dangling_tags(
+        &mut self,
+        objects: &[ObjectId],
+        morphisms: &[MorphismId],
+    ) -> Vec<Action<O, M, E>> {
+        let is_dangling = |(object_refs, morphism_refs): (Vec<&ObjectId>, Vec<&MorphismId>)| {
+            object_refs.into_iter().any(|id| objects.contains(id))
+                || morphism_refs.into_iter().any(|id| morphisms.contains(id))
+        };
+
+        // The tags are restored by removing the kept ones and adding all of them back
+        let mut kept_object_tags = Vec::new();
+        let mut all_object_tags = Vec::new();
+        for (&id, object) in self.objects.iter_mut() {
+            if !object.tags.iter().any(|tag| is_dangling(tag.references())) {
+                continue;
+            }
+            let all = std::mem::take(&mut object.tags);
+            object.tags = all
+                .iter()
+                .filter(|tag| !is_dangling(tag.references()))
+                .cloned()
+                .collect();
+            if !object.tags.is_empty() {
+                kept_object_tags.push((id, object.tags.clone()));
+            }
+            all_object_tags.push((id, all));
+        }
+
+        let mut kept_morphism_tags = Vec::new();
+        let mut all_morphism_tags = Vec::new();
+        for (&id, morphism) in self.morphisms.iter_mut() {
+            if !morphism
+                .tags
+                .iter()
+                .any(|tag| is_dangling(tag.references()))
+            {
+                continue;
+            }
+            let all = std::mem::take(morphism.tags);
+            *morphism.tags = all
+                .iter()
+                .filter(|tag| !is_dangling(tag.references()))
+                .cloned()
+                .collect();
+            if !morphism.tags.is_empty() {
+                kept_morphism_tags.push((id, morphism.tags.clone()));
+            }
+            all_morphism_tags.push((id, all));
+        }
+
+        let mut undo = Vec::new();
+        if !kept_object_tags.is_empty() {
+            undo.push(Action::RemoveObjectTags(kept_object_tags));
+        }
+        if !all_object_tags.is_empty() {
+            undo.push(Action::ExtendObjectTags(all_object_tags));
+        }
+        if !kept_morphism_tags.is_empty() {
+            undo.push(Action::RemoveMorphismTags(kept_morphism_tags));
+        }
+        if !all_morphism_tags.is_empty() {
+            undo.push(Action::ExtendMorphismTags(all_morphism_tags));
+        }
+        undo
+    }
+}
+
+/// Returns the ids of the objects and the morphisms that the undo actions remove,
+/// i.e. the ones created by the undone actions, in sorted order.
+pub(crate) fn created_ids<O, M, E>(undo: &[Action<O, M, E>]) -> (Vec<ObjectId>, Vec<MorphismId>) {
+    let mut objects = Vec::new();
+    let mut morphisms = Vec::new();
+    for action in undo {
+        match action {
+            Action::RemoveObjects(ids) => objects.extend(ids),
+            Action::RemoveMorphisms(ids) => morphisms.extend(ids),
+            _ => (),
         }
     }
+    objects.sort();
+    morphisms.sort();
+    (objects, morphisms)
 }
@@ -46,14 +46,19 @@ impl<O, M, E, L: Label> CategoryBuilder<O, M, E, L> {
     pub fn object<T: Into<L>>(
         mut self,
         label: T,
-        tags: impl IntoIterator<Item = ObjectTag<T>>,
+        tags: impl IntoIterator<Item = ObjectTag<T, T>>,
         inner: O,
     ) -> Self {
         let label = label.into();
         let new_object = self.category.new_object(Object {
             tags: tags
                 .into_iter()
-                .map(|tag| tag.map(|label| self.objects[&label.into()]))
+                .map(|tag| {
+                    tag.map(
+                        |label| self.objects[&label.into()],
+                        |label| self.morphisms[&label.into()],
+                    )
+                })
                 .collect(),
             inner,
         });
@@ -1,15 +1,19 @@
 use super::*;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum CategoryThing {
     Object { id: ObjectId },
     Morphism { id: MorphismId },
 }
 
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Category<O, M, E> {
     pub objects: Objects<O>,
     pub morphisms: Morphisms<M>,
     pub equalities: Equalities<E>,
+    /// Where the things added by rules came from.
+    #[serde(default)]
+    pub provenance: Provenance,
 }
 
 impl<O, M, E> Category<O, M, E> {
@@ -18,6 +22,7 @@ impl<O, M, E> Category<O, M, E> {
             objects: Objects::new(),
             morphisms: Morphisms::new(),
             equalities: Equalities::new(),
+            provenance: Provenance::new(),
         }
     }
 
@@ -52,17 +57,14 @@ impl<O, M, E> Category<O, M, E> {
     }
 
     /// Removes the object and connected morphisms from the graph.
+    /// Equalities and tags referring to them are left in place,
+    /// use [`Action::RemoveObjects`] to remove them as well.
     pub fn remove_object(
         &mut self,
         object_id: ObjectId,
     ) -> Option<(Object<O>, Vec<(MorphismId, Morphism<M>)>)> {
         self.objects.remove(&object_id).map(|object| {
-            let removes: Vec<_> = self
-                .morphisms
-                .iter()
-                .filter(|(_, morphism)| morphism.connection.is_object_connected(object_id))
-                .map(|(&id, _)| id)
-                .collect();
+            let removes: Vec<_> = self.morphisms.connected(object_id).collect();
             let mut morphisms = Vec::new();
             for remove in removes {
                 morphisms.push((remove, self.morphisms.remove(&remove).unwrap()));
@@ -77,15 +79,16 @@ impl<O, M, E> Category<O, M, E> {
     }
 
     pub fn neighbours<'a>(&'a self, object: ObjectId) -> impl Iterator<Item = ObjectId> + 'a {
-        self.morphisms.iter().filter_map(move |(_, morphism)| {
-            let endpoints = morphism.connection.end_points();
-            if *endpoints[0] == object {
-                Some(*endpoints[1])
-            } else if *endpoints[1] == object {
-                Some(*endpoints[0])
-            } else {
-                None
-            }
-        })
+        self.morphisms
+            .connected(object)
+            .filter_map(move |id| self.morphisms.get(&id))
+            .map(move |morphism| {
+                let endpoints = morphism.connection.end_points();
+                if *endpoints[0] == object {
+                    *endpoints[1]
+                } else {
+                    *endpoints[0]
+                }
+            })
     }
 }
@@ -0,0 +1,401 @@
+use std::collections::HashSet;
+
+use super::*;
+
+/// The limits of [`Category::find_countermodel`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CountermodelBudget {
+    /// The maximum number of elements in the set of an object.
+    pub max_size: usize,
+    /// The maximum number of partial models checked during the search.
+    pub max_models: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CountermodelOutcome {
+    /// The model satisfies the facts but violates the goal,
+    /// so the goal does not follow from the facts.
+    Found(FinSetModel),
+    /// No model with sets of at most `max_size` elements violates the goal.
+    NotFound,
+    /// The search ran out of models to check.
+    OutOfBudget,
+}
+
+/// A tag that finite-set models cannot interpret,
+/// so a model would not have to satisfy everything that follows from the facts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UnsupportedTag {
+    Object {
+        object: ObjectId,
+        tag: ObjectTag,
+    },
+    Morphism {
+        morphism: MorphismId,
+        tag: MorphismTag,
+    },
+}
+
+impl<O, M, E> Category<O, M, E> {
+    /// Searches for a finite-set model of the category (the facts)
+    /// in which the goal constraints fail for the things they are bound to.
+    /// Such a model shows that the goal cannot be proven from the facts.
+    ///
+    /// Only the goal constraints whose labels are all bound are considered,
+    /// with the tags that finite-set models can interpret.
+    pub fn find_countermodel(
+        &self,
+        goal: &Constraints<CategoryThing>,
+        bindings: &Bindings<CategoryThing>,
+        budget: &CountermodelBudget,
+    ) -> Result<CountermodelOutcome, UnsupportedTag> {
+        self.check_supported_tags()?;
+
+        let goal = Goal::new(goal, bindings);
+        let goal_morphisms = goal.morphisms();
+
+        let mut objects: Vec<_> = self.objects.iter().map(|(&id, _)| id).collect();
+        objects.sort();
+        // Assign the goal first, so that the search can backtrack
+        // as soon as the goal holds
+        let mut morphisms: Vec<_> = self.morphisms.iter().map(|(&id, _)| id).collect();
+        morphisms.sort_by_key(|id| (!goal_morphisms.contains(id), *id));
+
+        let mut search = Search {
+            category: self,
+            goal,
+            goal_morphisms,
+            objects,
+            morphisms,
+            budget,
+            model: FinSetModel::new(),
+            checked: 0,
+        };
+        Ok(match search.assign_object(0) {
+            Visit::Found => CountermodelOutcome::Found(search.model),
+            Visit::NotFound => CountermodelOutcome::NotFound,
+            Visit::OutOfBudget => CountermodelOutcome::OutOfBudget,
+        })
+    }
+
+    fn check_supported_tags(&self) -> Result<(), UnsupportedTag> {
+        let mut objects: Vec<_> = self.objects.iter().collect();
+        objects.sort_by_key(|(&id, _)| id);
+        for (&object, data) in objects {
+            for tag in &data.tags {
+                if !matches!(
+                    tag,
+                    ObjectTag::Initial | ObjectTag::Terminal | ObjectTag::Product(..)
+                ) {
+                    return Err(UnsupportedTag::Object { object, tag: *tag });
+                }
+            }
+        }
+
+        let mut morphisms: Vec<_> = self.morphisms.iter().collect();
+        morphisms.sort_by_key(|(&id, _)| id);
+        for (&morphism, data) in morphisms {
+            for tag in &data.tags {
+                if !matches!(
+                    tag,
+                    MorphismTag::Identity(_)
+                        | MorphismTag::Unique
+                        | MorphismTag::Composition { .. }
+                        | MorphismTag::Isomorphism(..)
+                        | MorphismTag::ProductP1
+                        | MorphismTag::ProductP2
+                ) {
+                    return Err(UnsupportedTag::Morphism {
+                        morphism,
+                        tag: *tag,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+enum Visit {
+    Found,
+    NotFound,
+    OutOfBudget,
+}
+
+/// The goal constraints whose labels are all bound, in terms of the fact ids.
+struct Goal {
+    objects: Vec<(ObjectId, Vec<ObjectTag>)>,
+    morphisms: Vec<(MorphismId, Vec<MorphismTag>)>,
+    equalities: Vec<Equality>,
+}
+
+impl Goal {
+    fn new(constraints: &Constraints<CategoryThing>, bindings: &Bindings<CategoryThing>) -> Self {
+        let object = |label: &CategoryThing| bindings.get_object(label);
+        let morphism = |label: &CategoryThing| bindings.get_morphism(label);
+
+        let mut goal = Self {
+            objects: Vec::new(),
+            morphisms: Vec::new(),
+            equalities: Vec::new(),
+        };
+        for constraint in constraints {
+            match constraint {
+                Constraint::Object { label, tags } => {
+                    let Some(id) = object(label) else {
+                        continue;
+                    };
+                    let tags: Vec<_> = tags
+                        .iter()
+                        .filter_map(|tag| match *tag {
+                            ObjectTag::Initial => Some(ObjectTag::Initial),
+                            ObjectTag::Terminal => Some(ObjectTag::Terminal),
+                            ObjectTag::Product(a, b) => {
+                                Some(ObjectTag::Product(object(&a)?, object(&b)?))
+                            }
+                            _ => None,
+                        })
+                        .collect();
+                    if !tags.is_empty() {
+                        goal.objects.push((id, tags));
+                    }
+                }
+                Constraint::Morphism { label, tags, .. } => {
+                    let Some(id) = morphism(label) else {
+                        continue;
+                    };
+                    let tags: Vec<_> = tags
+                        .iter()
+                        .filter_map(|tag| match *tag {
+                            MorphismTag::Identity(a) => Some(MorphismTag::Identity(object(&a)?)),
+                            MorphismTag::Unique => Some(MorphismTag::Unique),
+                            MorphismTag::Composition { first, second } => {
+                                Some(MorphismTag::Composition {
+                                    first: morphism(&first)?,
+                                    second: morphism(&second)?,
+                                })
+                            }
+                            _ => None,
+                        })
+                        .collect();
+                    if !tags.is_empty() {
+                        goal.morphisms.push((id, tags));
+                    }
+                }
+                Constraint::Equality(equality) => {
+                    let bound = |side: &Vec<CategoryThing>| side.iter().map(&morphism).collect();
+                    if let (Some(left), Some(right)) =
+                        (bound(equality.left()), bound(equality.right()))
+                    {
+                        goal.equalities.push(
+                            Equality::new(left, right)
+                                .expect("Equalities are expected to be valid"),
+                        );
+                    }
+                }
+            }
+        }
+        goal
+    }
+
+    /// The morphisms that the goal refers to.
+    fn morphisms(&self) -> HashSet<MorphismId> {
+        self.morphisms
+            .iter()
+            .flat_map(|(id, tags)| {
+                std::iter::once(*id).chain(tags.iter().flat_map(|tag| {
+                    let (_, morphisms) = tag.references();
+                    morphisms.into_iter().copied().collect::<Vec<_>>()
+                }))
+            })
+            .chain(
+                self.equalities
+                    .iter()
+                    .flat_map(|equality| equality.left().iter().chain(equality.right()).copied()),
+            )
+            .collect()
+    }
+
+    /// Whether some constraint fails in the model,
+    /// considering only the constraints that the model interprets.
+    fn is_violated<O, M, E>(&self, category: &Category<O, M, E>, model: &FinSetModel) -> bool {
+        let size = |id: &ObjectId| model.objects.get(id).copied();
+
+        let objects = self.objects.iter().any(|(id, tags)| {
+            let Some(object_size) = size(id) else {
+                return false;
+            };
+            tags.iter().any(|tag| match *tag {
+                ObjectTag::Initial => object_size != 0,
+                ObjectTag::Terminal => object_size != 1,
+                ObjectTag::Product(a, b) => match (size(&a), size(&b)) {
+                    (Some(size_a), Some(size_b)) => object_size != size_a * size_b,
+                    _ => false,
+                },
+                _ => false,
+            })
+        });
+
+        let morphisms = self.morphisms.iter().any(|(id, tags)| {
+            let (Some(function), Some(morphism)) =
+                (model.morphisms.get(id), category.morphisms.get(id))
+            else {
+                return false;
+            };
+            let [&from, &to] = morphism.connection.end_points();
+            tags.iter().any(|tag| match *tag {
+                MorphismTag::Identity(object) => {
+                    from == object && to == object && (0..function.len()).any(|x| function[x] != x)
+                }
+                MorphismTag::Unique => match (size(&from), size(&to)) {
+                    (Some(domain), Some(codomain)) => finset::function_count(domain, codomain) != 1,
+                    _ => false,
+                },
+                MorphismTag::Composition { first, second } => {
+                    model.morphisms.contains_key(&first)
+                        && model.morphisms.contains_key(&second)
+                        && category
+                            .composition_counterexample(model, from, function, first, second)
+                            .is_some()
+                }
+                _ => false,
+            })
+        });
+
+        let equalities = self.equalities.iter().any(|equality| {
+            equality
+                .left()
+                .iter()
+                .chain(equality.right())
+                .all(|id| model.morphisms.contains_key(id))
+                && category.equality_counterexample(model, equality).is_some()
+        });
+
+        objects || morphisms || equalities
+    }
+}
+
+struct Search<'a, O, M, E> {
+    category: &'a Category<O, M, E>,
+    goal: Goal,
+    goal_morphisms: HashSet<MorphismId>,
+    objects: Vec<ObjectId>,
+    /// The morphisms referred to by the goal come first.
+    morphisms: Vec<MorphismId>,
+    budget: &'a CountermodelBudget,
+    model: FinSetModel,
+    checked: usize,
+}
+
+impl<'a, O, M, E> Search<'a, O, M, E> {
+    fn assign_object(&mut self, index: usize) -> Visit {
+        let Some(&id) = self.objects.get(index) else {
+            return self.assign_morphism(0);
+        };
+        for size in 0..=self.budget.max_size {
+            self.model.objects.insert(id, size);
+            match self.check() {
+                None => return Visit::OutOfBudget,
+                Some(false) => continue,
+                Some(true) => (),
+            }
+            match self.assign_object(index + 1) {
+                Visit::NotFound => (),
+                visit => return visit,
+            }
+        }
+        self.model.objects.remove(&id);
+        Visit::NotFound
+    }
+
+    fn assign_morphism(&mut self, index: usize) -> Visit {
+        let Some(&id) = self.morphisms.get(index) else {
+            return if self.goal.is_violated(self.category, &self.model) {
+                Visit::Found
+            } else {
+                Visit::NotFound
+            };
+        };
+
+        for function in self.candidates(id) {
+            self.model.morphisms.insert(id, function);
+            match self.check() {
+                None => return Visit::OutOfBudget,
+                Some(false) => continue,
+                Some(true) => (),
+            }
+            match self.assign_morphism(index + 1) {
+                Visit::NotFound => (),
+                visit => return visit,
+            }
+        }
+        self.model.morphisms.remove(&id);
+        Visit::NotFound
+    }
+
+    /// Returns the functions worth trying for the morphism.
+    fn candidates(&self, id: MorphismId) -> Box<dyn Iterator<Item = Vec<usize>>> {
+        let morphism = self
+            .category
+            .morphisms
+            .get(&id)
+            .expect("Morphisms are expected to exist");
+        let [from, to] = morphism.connection.end_points();
+        let domain = self.model.objects[from];
+        let codomain = self.model.objects[to];
+
+        // Identities and compositions are determined by the rest
+        for tag in &morphism.tags {
+            match *tag {
+                MorphismTag::Identity(_) => {
+                    return Box::new(std::iter::once((0..domain).collect()));
+                }
+                MorphismTag::Composition { first, second }
+                    if self.model.morphisms.contains_key(&first)
+                        && self.model.morphisms.contains_key(&second) =>
+                {
+                    let function: Option<Vec<_>> = (0..domain)
+                        .map(|x| {
+                            self.category
+                                .eval_path(&self.model, &[first, second], *from, x)
+                        })
+                        .collect();
+                    return Box::new(function.into_iter());
+                }
+                _ => (),
+            }
+        }
+
+        Box::new(
+            (0..finset::function_count(domain, codomain)).map(move |mut index| {
+                (0..domain)
+                    .map(|_| {
+                        let image = index % codomain;
+                        index /= codomain;
+                        image
+                    })
+                    .collect()
+            }),
+        )
+    }
+
+    /// Checks the current partial model.
+    /// Returns `None` if the budget has run out.
+    fn check(&mut self) -> Option<bool> {
+        if self.checked >= self.budget.max_models {
+            return None;
+        }
+        self.checked += 1;
+
+        if self.category.check_partial_model(&self.model).is_err() {
+            return Some(false);
+        }
+        // Once the goal is fully interpreted, the rest cannot violate it anymore
+        let goal_assigned = self.model.objects.len() == self.objects.len()
+            && self
+                .goal_morphisms
+                .iter()
+                .all(|id| self.model.morphisms.contains_key(id));
+        Some(!goal_assigned || self.goal.is_violated(self.category, &self.model))
+    }
+}
@@ -2,11 +2,12 @@ use std::collections::HashMap;
 
 use super::*;
 
+#[derive(Clone)]
 pub struct Equalities<T> {
     inner: HashMap<Equality, T>,
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize)]
 pub struct Equality<M = MorphismId> {
     left: Vec<M>,
     right: Vec<M>,
@@ -43,6 +44,50 @@ impl<M> Equality<M> {
     pub fn right(&self) -> &Vec<M> {
         &self.right
     }
+
+    /// Returns the equality in the opposite category,
+    /// where both compositions are taken in the reverse order.
+    pub fn dual(self) -> Self
+    where
+        M: Ord,
+    {
+        let (mut left, mut right) = self.destructure();
+        left.reverse();
+        right.reverse();
+        Self::new(left, right).expect("Reversing an equality preserves its validity")
+    }
+}
+
+#[derive(Deserialize)]
+struct EqualityRepr<M> {
+    left: Vec<M>,
+    right: Vec<M>,
+}
+
+impl<'de, M: Ord + Deserialize<'de>> Deserialize<'de> for Equality<M> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = EqualityRepr::deserialize(deserializer)?;
+        Self::new(repr.left, repr.right)
+            .map_err(|()| serde::de::Error::custom("both sides of an equality must be non-empty"))
+    }
+}
+
+impl<T: Serialize> Serialize for Equalities<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // Sort to keep the output stable
+        let mut equalities: Vec<_> = self.inner.iter().collect();
+        equalities.sort_by_key(|&(equality, _)| equality);
+        equalities.serialize(serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Equalities<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let equalities = Vec::<(Equality, T)>::deserialize(deserializer)?;
+        Ok(Self {
+            inner: equalities.into_iter().collect(),
+        })
+    }
 }
 
 impl<T> Equalities<T> {
@@ -0,0 +1,269 @@
+use std::collections::{HashMap, VecDeque};
+
+use super::*;
+
+/// A justification of the equality of two paths:
+/// both paths are rewritten step by step into the same path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EqualityProof {
+    /// Steps rewriting the left path into the common path.
+    pub left: Vec<ProofStep>,
+    /// Steps rewriting the right path into the common path.
+    pub right: Vec<ProofStep>,
+}
+
+/// A rewrite together with the path it produces.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofStep {
+    pub rewrite: Rewrite,
+    pub result: Path,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rewrite {
+    /// One side of a stored equality, found at the position in the path,
+    /// is replaced with the other side.
+    /// If `reversed`, the right side of the equality is replaced with the left one.
+    Equality {
+        equality: Equality,
+        position: usize,
+        reversed: bool,
+    },
+    /// A composite morphism is replaced with its two parts.
+    Decompose {
+        position: usize,
+        morphism: MorphismId,
+    },
+    /// An identity is removed from the path.
+    RemoveIdentity {
+        position: usize,
+        morphism: MorphismId,
+    },
+}
+
+impl<O, M, E> Category<O, M, E> {
+    /// Finds a chain of rewrites showing that two paths are equal,
+    /// exploring at most `budget` paths.
+    /// Returns `None` if the paths are not equal, if neither side can be rewritten further,
+    /// or if the budget runs out before the chain is found.
+    pub fn prove_equality(
+        &self,
+        left: &[MorphismId],
+        right: &[MorphismId],
+        budget: usize,
+    ) -> Option<EqualityProof> {
+        if self.solve_paths(left, right, DEFAULT_SOLVER_BUDGET) == PathEquality::NotEqual {
+            return None;
+        }
+
+        let mut sides = [
+            Side::new(self.normalize_with_steps(left.to_vec())),
+            Side::new(self.normalize_with_steps(right.to_vec())),
+        ];
+        let mut common = sides[0].queue[0].clone();
+        let mut explored = 2;
+        while !sides[1].visited.contains_key(&common) {
+            // Expand the smaller side that still has paths to explore
+            let this = (0..2)
+                .filter(|&side| !sides[side].queue.is_empty())
+                .min_by_key(|&side| sides[side].queue.len())?;
+            let path = sides[this].queue.pop_front()?;
+            for (rewrite, rewritten) in self.equality_rewrites(&path) {
+                let (mut steps, next) = self.normalize_with_steps(rewritten.clone());
+                if sides[this].visited.contains_key(&next) {
+                    continue;
+                }
+                explored += 1;
+                if explored > budget {
+                    return None;
+                }
+
+                steps.insert(
+                    0,
+                    ProofStep {
+                        rewrite,
+                        result: rewritten,
+                    },
+                );
+                sides[this]
+                    .visited
+                    .insert(next.clone(), Some((path.clone(), steps)));
+                if sides[1 - this].visited.contains_key(&next) {
+                    common = next;
+                    break;
+                }
+                sides[this].queue.push_back(next);
+            }
+        }
+
+        Some(EqualityProof {
+            left: sides[0].chain(&common),
+            right: sides[1].chain(&common),
+        })
+    }
+
+    /// Decomposes compositions and removes identities one step at a time,
+    /// keeping the last identity if nothing else is left.
+    fn normalize_with_steps(&self, mut path: Path) -> (Vec<ProofStep>, Path) {
+        let mut steps = Vec::new();
+        loop {
+            let rewrite = path.iter().enumerate().find_map(|(position, &morphism)| {
+                if path.len() > 1 && self.is_identity(&morphism) {
+                    return Some(Rewrite::RemoveIdentity { position, morphism });
+                }
+                self.morphisms
+                    .get(&morphism)?
+                    .tags
+                    .iter()
+                    .any(|tag| matches!(tag, MorphismTag::Composition { .. }))
+                    .then_some(Rewrite::Decompose { position, morphism })
+            });
+            let Some(rewrite) = rewrite else {
+                return (steps, path);
+            };
+            path = self
+                .apply_rewrite(&path, &rewrite)
+                .expect("The rewrite has been found in the path");
+            steps.push(ProofStep {
+                rewrite,
+                result: path.clone(),
+            });
+        }
+    }
+
+    /// Returns all paths obtained by rewriting the path with a stored equality once.
+    fn equality_rewrites(&self, path: &[MorphismId]) -> Vec<(Rewrite, Path)> {
+        // Sort to keep the proofs stable
+        let mut equalities: Vec<_> = self.equalities.iter_equalities().collect();
+        equalities.sort();
+
+        let mut rewrites = Vec::new();
+        for equality in equalities {
+            for reversed in [false, true] {
+                let from = if reversed {
+                    equality.right()
+                } else {
+                    equality.left()
+                };
+                for position in 0..=path.len().saturating_sub(from.len()) {
+                    let rewrite = Rewrite::Equality {
+                        equality: equality.clone(),
+                        position,
+                        reversed,
+                    };
+                    if let Some(next) = self.apply_rewrite(path, &rewrite) {
+                        rewrites.push((rewrite, next));
+                    }
+                }
+            }
+        }
+        rewrites
+    }
+
+    /// Applies the rewrite to the path,
+    /// or returns `None` if it cannot be applied at that position.
+    pub fn apply_rewrite(&self, path: &[MorphismId], rewrite: &Rewrite) -> Option<Path> {
+        let (position, from, to) = match rewrite {
+            Rewrite::Equality {
+                equality,
+                position,
+                reversed,
+            } => {
+                if !self.equalities.contains_equality(equality) {
+                    return None;
+                }
+                let (from, to) = if *reversed {
+                    (equality.right(), equality.left())
+                } else {
+                    (equality.left(), equality.right())
+                };
+                (*position, from.clone(), to.clone())
+            }
+            &Rewrite::Decompose { position, morphism } => {
+                let (first, second) =
+                    self.morphisms
+                        .get(&morphism)?
+                        .tags
+                        .iter()
+                        .find_map(|tag| match *tag {
+                            MorphismTag::Composition { first, second } => Some((first, second)),
+                            _ => None,
+                        })?;
+                (position, vec![morphism], vec![first, second])
+            }
+            &Rewrite::RemoveIdentity { position, morphism } => {
+                if path.len() < 2 || !self.is_identity(&morphism) {
+                    return None;
+                }
+                (position, vec![morphism], vec![])
+            }
+        };
+
+        if !path.get(position..)?.starts_with(&from) {
+            return None;
+        }
+        Some(
+            path[..position]
+                .iter()
+                .chain(&to)
+                .chain(&path[position + from.len()..])
+                .copied()
+                .collect(),
+        )
+    }
+}
+
+impl EqualityProof {
+    /// Checks that every step is a valid rewrite in the category,
+    /// and that both chains lead from the given paths to the same path.
+    pub fn verify<O, M, E>(
+        &self,
+        category: &Category<O, M, E>,
+        left: &[MorphismId],
+        right: &[MorphismId],
+    ) -> bool {
+        let replay = |start: &[MorphismId], steps: &[ProofStep]| {
+            steps.iter().try_fold(start.to_vec(), |path, step| {
+                category
+                    .apply_rewrite(&path, &step.rewrite)
+                    .filter(|result| *result == step.result)
+            })
+        };
+        match (replay(left, &self.left), replay(right, &self.right)) {
+            (Some(left), Some(right)) => left == right,
+            _ => false,
+        }
+    }
+}
+
+/// One side of the bidirectional search.
+struct Side {
+    /// Every explored path with the path it was rewritten from and the steps taken.
+    visited: HashMap<Path, Option<(Path, Vec<ProofStep>)>>,
+    queue: VecDeque<Path>,
+    /// Steps normalizing the starting path.
+    start: Vec<ProofStep>,
+}
+
+impl Side {
+    fn new((start, path): (Vec<ProofStep>, Path)) -> Self {
+        Self {
+            visited: HashMap::from([(path.clone(), None)]),
+            queue: VecDeque::from([path]),
+            start,
+        }
+    }
+
+    /// Returns the steps leading from the start to the path.
+    fn chain(&self, path: &Path) -> Vec<ProofStep> {
+        let mut chain = Vec::new();
+        let mut current = path;
+        while let Some(Some((parent, steps))) = self.visited.get(current) {
+            chain.extend(steps.iter().rev().cloned());
+            current = parent;
+        }
+        chain.extend(self.start.iter().rev().cloned());
+        chain.reverse();
+        chain
+    }
+}
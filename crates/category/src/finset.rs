@@ -0,0 +1,383 @@
+use std::collections::{HashMap, HashSet};
+
+use super::*;
+
+/// An interpretation of a category in finite sets:
+/// an object is the set `0..size`, and a morphism is the function
+/// mapping each element `x` of its domain to `images[x]`.
+/// A morphism with an isomorphism connection `(a, b)` is a function from `a` to `b`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FinSetModel {
+    /// The size of the set of each object.
+    pub objects: HashMap<ObjectId, usize>,
+    /// The images of the elements under each morphism.
+    pub morphisms: HashMap<MorphismId, Vec<usize>>,
+}
+
+/// The first reason why a [`FinSetModel`] is not a model of a category.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModelViolation {
+    MissingObject {
+        object: ObjectId,
+    },
+    MissingMorphism {
+        morphism: MorphismId,
+    },
+    /// The function is not defined on exactly the elements of the domain.
+    NotAFunction {
+        morphism: MorphismId,
+    },
+    /// The element is mapped outside of the codomain.
+    OutOfCodomain {
+        morphism: MorphismId,
+        element: usize,
+    },
+    /// The isomorphism is not a bijection.
+    NotBijective {
+        morphism: MorphismId,
+    },
+    /// The identity moves the element.
+    Identity {
+        morphism: MorphismId,
+        element: usize,
+    },
+    /// There is not exactly one function between the sets of the end points.
+    Unique {
+        morphism: MorphismId,
+    },
+    /// The composition of the parts maps the element differently.
+    Composition {
+        morphism: MorphismId,
+        element: usize,
+    },
+    /// The element of the object does not return to itself
+    /// when passed through the isomorphism and its inverse.
+    Isomorphism {
+        morphism: MorphismId,
+        object: ObjectId,
+        element: usize,
+    },
+    /// The set of a terminal object does not have exactly one element.
+    Terminal {
+        object: ObjectId,
+    },
+    /// The set of an initial object is not empty.
+    Initial {
+        object: ObjectId,
+    },
+    /// The product has the wrong size (`element` is `None`),
+    /// or the projections map the element to the same pair as an earlier one.
+    Product {
+        object: ObjectId,
+        element: Option<usize>,
+    },
+    /// The sides of the equality map the element of the object differently.
+    Equality {
+        equality: Equality,
+        object: ObjectId,
+        element: usize,
+    },
+}
+
+impl FinSetModel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<O, M, E> Category<O, M, E> {
+    /// Checks that the model interprets every object and morphism,
+    /// and that identities, unique morphisms, compositions, isomorphisms, products,
+    /// terminal and initial objects, and all equalities hold in it.
+    /// Other tags are not checked.
+    pub fn check_model(&self, model: &FinSetModel) -> Result<(), ModelViolation> {
+        self.check_model_impl(model, false)
+    }
+
+    /// Like [`Category::check_model`], but skips everything
+    /// that refers to objects or morphisms not interpreted by the model.
+    pub(crate) fn check_partial_model(&self, model: &FinSetModel) -> Result<(), ModelViolation> {
+        self.check_model_impl(model, true)
+    }
+
+    fn check_model_impl(&self, model: &FinSetModel, partial: bool) -> Result<(), ModelViolation> {
+        let mut objects: Vec<_> = self.objects.iter().collect();
+        objects.sort_by_key(|(&id, _)| id);
+        let mut morphisms: Vec<_> = self.morphisms.iter().collect();
+        morphisms.sort_by_key(|(&id, _)| id);
+        let mut equalities: Vec<_> = self.equalities.iter_equalities().collect();
+        equalities.sort();
+
+        let size = |id: &ObjectId| model.objects.get(id).copied();
+        let is_interpreted = |ids: &[&MorphismId]| {
+            ids.iter()
+                .all(|id| model.morphisms.contains_key(id) && self.morphisms.contains(id))
+        };
+
+        // The interpretation itself
+        if !partial {
+            if let Some((&object, _)) = objects.iter().find(|(id, _)| size(id).is_none()) {
+                return Err(ModelViolation::MissingObject { object });
+            }
+        }
+        for &(&id, morphism) in &morphisms {
+            let Some(function) = model.morphisms.get(&id) else {
+                if partial {
+                    continue;
+                }
+                return Err(ModelViolation::MissingMorphism { morphism: id });
+            };
+            let [from, to] = morphism.connection.end_points();
+            let (Some(domain), Some(codomain)) = (size(from), size(to)) else {
+                continue;
+            };
+            if function.len() != domain {
+                return Err(ModelViolation::NotAFunction { morphism: id });
+            }
+            if let Some(element) = function.iter().position(|&image| image >= codomain) {
+                return Err(ModelViolation::OutOfCodomain {
+                    morphism: id,
+                    element,
+                });
+            }
+            if let MorphismConnection::Isomorphism(..) = morphism.connection {
+                let images: HashSet<_> = function.iter().collect();
+                if domain != codomain || images.len() != domain {
+                    return Err(ModelViolation::NotBijective { morphism: id });
+                }
+            }
+        }
+
+        for &(&id, object) in &objects {
+            let Some(object_size) = size(&id) else {
+                continue;
+            };
+            for tag in &object.tags {
+                match *tag {
+                    ObjectTag::Terminal if object_size != 1 => {
+                        return Err(ModelViolation::Terminal { object: id });
+                    }
+                    ObjectTag::Initial if object_size != 0 => {
+                        return Err(ModelViolation::Initial { object: id });
+                    }
+                    ObjectTag::Product(a, b) => self.check_product(model, id, a, b)?,
+                    _ => (),
+                }
+            }
+        }
+
+        for &(&id, morphism) in &morphisms {
+            let Some(function) = model.morphisms.get(&id) else {
+                continue;
+            };
+            let [&from, &to] = morphism.connection.end_points();
+            for tag in &morphism.tags {
+                match *tag {
+                    MorphismTag::Unique => {
+                        if let (Some(domain), Some(codomain)) = (size(&from), size(&to)) {
+                            if function_count(domain, codomain) != 1 {
+                                return Err(ModelViolation::Unique { morphism: id });
+                            }
+                        }
+                    }
+                    MorphismTag::Identity(_) => {
+                        if let Some(element) = (0..function.len()).find(|&x| function[x] != x) {
+                            return Err(ModelViolation::Identity {
+                                morphism: id,
+                                element,
+                            });
+                        }
+                    }
+                    MorphismTag::Composition { first, second } => {
+                        if !is_interpreted(&[&first, &second]) {
+                            continue;
+                        }
+                        if let Some(element) =
+                            self.composition_counterexample(model, from, function, first, second)
+                        {
+                            return Err(ModelViolation::Composition {
+                                morphism: id,
+                                element,
+                            });
+                        }
+                    }
+                    MorphismTag::Isomorphism(f, g) => {
+                        if !is_interpreted(&[&f, &g]) {
+                            continue;
+                        }
+                        for path in [[f, g], [g, f]] {
+                            for (start, _) in self
+                                .path_ends(&path)
+                                .into_iter()
+                                .filter(|(start, end)| start == end)
+                            {
+                                let element = (0..size(&start).unwrap_or(0))
+                                    .find(|&x| self.eval_path(model, &path, start, x) != Some(x));
+                                if let Some(element) = element {
+                                    return Err(ModelViolation::Isomorphism {
+                                        morphism: id,
+                                        object: start,
+                                        element,
+                                    });
+                                }
+                            }
+                        }
+                    }
+                    _ => (),
+                }
+            }
+        }
+
+        for equality in equalities {
+            let (left, right) = (equality.left(), equality.right());
+            let ids: Vec<_> = left.iter().chain(right).collect();
+            if !is_interpreted(&ids) {
+                continue;
+            }
+            if let Some((object, element)) = self.equality_counterexample(model, equality) {
+                return Err(ModelViolation::Equality {
+                    equality: equality.clone(),
+                    object,
+                    element,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the first element that the composition of the parts maps differently.
+    pub(crate) fn composition_counterexample(
+        &self,
+        model: &FinSetModel,
+        from: ObjectId,
+        function: &[usize],
+        first: MorphismId,
+        second: MorphismId,
+    ) -> Option<usize> {
+        (0..function.len())
+            .find(|&x| self.eval_path(model, &[first, second], from, x) != Some(function[x]))
+    }
+
+    /// Returns the first element, together with its object,
+    /// that the sides of the equality map differently.
+    pub(crate) fn equality_counterexample(
+        &self,
+        model: &FinSetModel,
+        equality: &Equality,
+    ) -> Option<(ObjectId, usize)> {
+        let (left, right) = (equality.left(), equality.right());
+        let right_ends = self.path_ends(right);
+        let mut starts: Vec<_> = self
+            .path_ends(left)
+            .into_iter()
+            .filter(|ends| right_ends.contains(ends))
+            .map(|(start, _)| start)
+            .collect();
+        starts.dedup();
+        starts.into_iter().find_map(|start| {
+            (0..model.objects.get(&start).copied().unwrap_or(0))
+                .find(|&x| {
+                    self.eval_path(model, left, start, x) != self.eval_path(model, right, start, x)
+                })
+                .map(|element| (start, element))
+        })
+    }
+
+    /// Checks that the object has the size of the product of the factors,
+    /// and that its projections, if there are any, pair the elements bijectively.
+    fn check_product(
+        &self,
+        model: &FinSetModel,
+        object: ObjectId,
+        a: ObjectId,
+        b: ObjectId,
+    ) -> Result<(), ModelViolation> {
+        let (Some(size), Some(size_a), Some(size_b)) = (
+            model.objects.get(&object),
+            model.objects.get(&a),
+            model.objects.get(&b),
+        ) else {
+            return Ok(());
+        };
+        if *size != size_a * size_b {
+            return Err(ModelViolation::Product {
+                object,
+                element: None,
+            });
+        }
+
+        let projection = |factor: ObjectId, first: bool| {
+            let mut projections: Vec<_> = self
+                .morphisms
+                .outgoing(object)
+                .filter(|id| {
+                    self.morphisms.get(id).is_some_and(|morphism| {
+                        morphism.connection
+                            == MorphismConnection::Regular {
+                                from: object,
+                                to: factor,
+                            }
+                            && morphism.tags.iter().any(|tag| {
+                                if first {
+                                    matches!(tag, MorphismTag::ProductP1)
+                                } else {
+                                    matches!(tag, MorphismTag::ProductP2)
+                                }
+                            })
+                    })
+                })
+                .collect();
+            projections.sort();
+            projections
+                .into_iter()
+                .find_map(|id| model.morphisms.get(&id))
+        };
+        if let (Some(p1), Some(p2)) = (projection(a, true), projection(b, false)) {
+            let mut pairs = HashSet::new();
+            if let Some(element) = (0..*size).find(|&x| !pairs.insert((p1.get(x), p2.get(x)))) {
+                return Err(ModelViolation::Product {
+                    object,
+                    element: Some(element),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Applies the functions along the path to the element of the start object.
+    /// Isomorphisms are passed backwards when entered from their second end point.
+    /// Returns `None` if the path does not compose from the start,
+    /// or if the model does not interpret it.
+    pub fn eval_path(
+        &self,
+        model: &FinSetModel,
+        path: &[MorphismId],
+        start: ObjectId,
+        element: usize,
+    ) -> Option<usize> {
+        path.iter()
+            .try_fold((start, element), |(object, element), id| {
+                let function = model.morphisms.get(id)?;
+                match self.morphisms.get(id)?.connection {
+                    MorphismConnection::Regular { from, to } if from == object => {
+                        function.get(element).map(|&image| (to, image))
+                    }
+                    MorphismConnection::Isomorphism(a, b) if a == object => {
+                        function.get(element).map(|&image| (b, image))
+                    }
+                    MorphismConnection::Isomorphism(a, b) if b == object => function
+                        .iter()
+                        .position(|&image| image == element)
+                        .map(|preimage| (a, preimage)),
+                    _ => None,
+                }
+            })
+            .map(|(_, element)| element)
+    }
+}
+
+/// The number of functions from a set of the domain size to a set of the codomain size.
+pub(crate) fn function_count(domain: usize, codomain: usize) -> usize {
+    u32::try_from(domain).map_or(usize::MAX, |domain| codomain.saturating_pow(domain))
+}
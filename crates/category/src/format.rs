@@ -0,0 +1,58 @@
+//! A stable, versioned text format for saving and loading
+//! categories, rules, actions, and anything else that implements
+//! [`Serialize`] and [`Deserialize`].
+//!
+//! The data is stored as JSON together with the version of the format:
+//! ```json
+//! { "version": 1, "data": ... }
+//! ```
+
+use serde::de::DeserializeOwned;
+
+use super::*;
+
+/// The current version of the format.
+pub const FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug)]
+pub enum FormatError {
+    Json(serde_json::Error),
+    UnsupportedVersion(u32),
+}
+
+impl From<serde_json::Error> for FormatError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Json(error)
+    }
+}
+
+#[derive(Serialize)]
+struct Versioned<T> {
+    version: u32,
+    data: T,
+}
+
+#[derive(Deserialize)]
+struct VersionedValue {
+    version: u32,
+    data: serde_json::Value,
+}
+
+/// Serializes the value into the text format.
+pub fn to_string<T: Serialize>(value: &T) -> Result<String, FormatError> {
+    let versioned = Versioned {
+        version: FORMAT_VERSION,
+        data: value,
+    };
+    Ok(serde_json::to_string_pretty(&versioned)?)
+}
+
+/// Deserializes the value from the text format.
+/// Fails if the text was produced by an unsupported version of the format.
+pub fn from_str<T: DeserializeOwned>(text: &str) -> Result<T, FormatError> {
+    let versioned: VersionedValue = serde_json::from_str(text)?;
+    if versioned.version != FORMAT_VERSION {
+        return Err(FormatError::UnsupportedVersion(versioned.version));
+    }
+    Ok(serde_json::from_value(versioned.data)?)
+}
@@ -0,0 +1,241 @@
+use std::collections::HashMap;
+
+use super::*;
+
+/// A mapping of objects and morphisms from one category to another.
+/// Use [`Functor::check`] to verify that the mapping satisfies the functor laws.
+#[derive(Debug, Clone)]
+pub struct Functor {
+    objects: HashMap<ObjectId, ObjectId>,
+    morphisms: HashMap<MorphismId, MorphismId>,
+}
+
+/// A violation of the functor laws.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FunctorError {
+    /// The object from the source category is not mapped.
+    UnmappedObject(ObjectId),
+    /// The morphism from the source category is not mapped.
+    UnmappedMorphism(MorphismId),
+    /// The object is mapped to an object that does not exist in the target category.
+    UnknownObject { object: ObjectId, image: ObjectId },
+    /// The morphism is mapped to a morphism that does not exist in the target category.
+    UnknownMorphism {
+        morphism: MorphismId,
+        image: MorphismId,
+    },
+    /// The image of the morphism does not connect the images of its end points.
+    Connection {
+        morphism: MorphismId,
+        expected: MorphismConnection,
+        actual: MorphismConnection,
+    },
+    /// The identity morphism is not mapped to the identity of the image object.
+    Identity {
+        morphism: MorphismId,
+        object: ObjectId,
+    },
+    /// The composition is not mapped to the composition of the images.
+    Composition {
+        morphism: MorphismId,
+        first: MorphismId,
+        second: MorphismId,
+    },
+    /// The equality does not hold between the images.
+    Equality(Equality),
+    /// The solver could not decide whether the composition is preserved.
+    UndecidedComposition {
+        morphism: MorphismId,
+        first: MorphismId,
+        second: MorphismId,
+    },
+    /// The solver could not decide whether the equality holds between the images.
+    UndecidedEquality(Equality),
+}
+
+impl Functor {
+    pub fn new() -> Self {
+        Self {
+            objects: HashMap::new(),
+            morphisms: HashMap::new(),
+        }
+    }
+}
+
+impl Default for Functor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Functor {
+    pub fn map_object(&mut self, object: ObjectId, image: ObjectId) -> Option<ObjectId> {
+        self.objects.insert(object, image)
+    }
+
+    pub fn map_morphism(&mut self, morphism: MorphismId, image: MorphismId) -> Option<MorphismId> {
+        self.morphisms.insert(morphism, image)
+    }
+
+    pub fn get_object(&self, object: &ObjectId) -> Option<ObjectId> {
+        self.objects.get(object).copied()
+    }
+
+    pub fn get_morphism(&self, morphism: &MorphismId) -> Option<MorphismId> {
+        self.morphisms.get(morphism).copied()
+    }
+
+    /// Checks that the functor preserves connections, identities, compositions, and equalities.
+    /// Returns every law that is violated,
+    /// and every law that the solver could not decide within its budget.
+    pub fn check<O, M, E, P, N, F>(
+        &self,
+        source: &Category<O, M, E>,
+        target: &Category<P, N, F>,
+    ) -> Result<(), Vec<FunctorError>> {
+        let mut errors = Vec::new();
+        let solver = LazySolver::new(target);
+
+        for (&object, _) in source.objects.iter() {
+            match self.get_object(&object) {
+                None => errors.push(FunctorError::UnmappedObject(object)),
+                Some(image) if !target.objects.contains(&image) => {
+                    errors.push(FunctorError::UnknownObject { object, image })
+                }
+                Some(_) => (),
+            }
+        }
+
+        for (&morphism_id, morphism) in source.morphisms.iter() {
+            let image_id = match self.get_morphism(&morphism_id) {
+                None => {
+                    errors.push(FunctorError::UnmappedMorphism(morphism_id));
+                    continue;
+                }
+                Some(image) => image,
+            };
+            let image = match target.morphisms.get(&image_id) {
+                None => {
+                    errors.push(FunctorError::UnknownMorphism {
+                        morphism: morphism_id,
+                        image: image_id,
+                    });
+                    continue;
+                }
+                Some(image) => image,
+            };
+
+            // Check connection
+            let expected = morphism
+                .connection
+                .map_borrowed(|object| self.get_object(object));
+            if let Some(expected) = transpose_connection(expected) {
+                let connected = match (&expected, &image.connection) {
+                    (
+                        MorphismConnection::Regular { from, to },
+                        MorphismConnection::Regular {
+                            from: image_from,
+                            to: image_to,
+                        },
+                    ) => from == image_from && to == image_to,
+                    (
+                        MorphismConnection::Isomorphism(a, b),
+                        MorphismConnection::Isomorphism(c, d),
+                    ) => a == c && b == d || a == d && b == c,
+                    _ => false,
+                };
+                if !connected {
+                    errors.push(FunctorError::Connection {
+                        morphism: morphism_id,
+                        expected,
+                        actual: image.connection,
+                    });
+                }
+            }
+
+            for tag in &morphism.tags {
+                match *tag {
+                    MorphismTag::Identity(object) => {
+                        let preserved = self.get_object(&object).is_some_and(|image_object| {
+                            image.tags.iter().any(|tag| match tag {
+                                MorphismTag::Identity(id) => *id == image_object,
+                                _ => false,
+                            })
+                        });
+                        if !preserved {
+                            errors.push(FunctorError::Identity {
+                                morphism: morphism_id,
+                                object,
+                            });
+                        }
+                    }
+                    MorphismTag::Composition { first, second } => {
+                        let preserved =
+                            match (self.get_morphism(&first), self.get_morphism(&second)) {
+                                (Some(image_first), Some(image_second)) => solver
+                                    .get()
+                                    .solve(&[image_id], &[image_first, image_second]),
+                                _ => PathEquality::NotEqual,
+                            };
+                        match preserved {
+                            PathEquality::Equal => (),
+                            PathEquality::NotEqual => errors.push(FunctorError::Composition {
+                                morphism: morphism_id,
+                                first,
+                                second,
+                            }),
+                            PathEquality::Unknown => {
+                                errors.push(FunctorError::UndecidedComposition {
+                                    morphism: morphism_id,
+                                    first,
+                                    second,
+                                })
+                            }
+                        }
+                    }
+                    _ => (),
+                }
+            }
+        }
+
+        for equality in source.equalities.iter_equalities() {
+            let map_path = |path: &Vec<MorphismId>| {
+                path.iter()
+                    .map(|morphism| self.get_morphism(morphism))
+                    .collect::<Option<Vec<_>>>()
+            };
+            let preserved = match (map_path(equality.left()), map_path(equality.right())) {
+                (Some(left), Some(right)) => solver.get().solve(&left, &right),
+                _ => PathEquality::NotEqual,
+            };
+            match preserved {
+                PathEquality::Equal => (),
+                PathEquality::NotEqual => errors.push(FunctorError::Equality(equality.clone())),
+                PathEquality::Unknown => {
+                    errors.push(FunctorError::UndecidedEquality(equality.clone()))
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+fn transpose_connection<T>(
+    connection: MorphismConnection<Option<T>>,
+) -> Option<MorphismConnection<T>> {
+    match connection {
+        MorphismConnection::Regular {
+            from: Some(from),
+            to: Some(to),
+        } => Some(MorphismConnection::Regular { from, to }),
+        MorphismConnection::Isomorphism(Some(a), Some(b)) => {
+            Some(MorphismConnection::Isomorphism(a, b))
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,276 @@
+use std::collections::{BTreeMap, HashMap};
+
+use super::*;
+
+impl<O, M, E> Category<O, M, E> {
+    /// Generalizes the category into a rule.
+    /// The given objects and morphisms (and the endpoints of the given morphisms)
+    /// become the `forall` part, and everything else the `exists` part.
+    ///
+    /// Since tags in `exists` constructions may only refer to labels introduced earlier,
+    /// the new objects and morphisms are split into as many constructions as needed.
+    /// All equalities are put into the last construction.
+    pub fn to_lemma<L: Label>(
+        &self,
+        is_given: impl Fn(CategoryThing) -> bool,
+        label: impl Fn(CategoryThing) -> L,
+    ) -> Result<Rule<L>, RuleConstructionError<L>> {
+        let object_label = |id: ObjectId| label(CategoryThing::Object { id });
+        let morphism_label = |id: MorphismId| label(CategoryThing::Morphism { id });
+
+        let mut object_ids: Vec<_> = self.objects.iter().map(|(&id, _)| id).collect();
+        object_ids.sort();
+        let mut morphism_ids: Vec<_> = self.morphisms.iter().map(|(&id, _)| id).collect();
+        morphism_ids.sort();
+
+        // The index of the construction where each thing is introduced, 0 being `forall`
+        let mut object_levels: HashMap<ObjectId, usize> = HashMap::new();
+        let mut morphism_levels: HashMap<MorphismId, usize> = HashMap::new();
+        for &id in &morphism_ids {
+            if is_given(CategoryThing::Morphism { id }) {
+                morphism_levels.insert(id, 0);
+                let morphism = self.morphisms.get(&id).unwrap();
+                for object in morphism.connection.end_points() {
+                    object_levels.insert(*object, 0);
+                }
+            }
+        }
+        for &id in &object_ids {
+            if is_given(CategoryThing::Object { id }) {
+                object_levels.insert(id, 0);
+            }
+        }
+        let given_objects: Vec<_> = object_ids
+            .iter()
+            .copied()
+            .filter(|id| object_levels.contains_key(id))
+            .collect();
+        let given_morphisms: Vec<_> = morphism_ids
+            .iter()
+            .copied()
+            .filter(|id| morphism_levels.contains_key(id))
+            .collect();
+
+        // Find the levels of the new things, each iteration resolving at least one more
+        // unless the tags refer to each other in a cycle
+        for _ in 0..=object_ids.len() + morphism_ids.len() {
+            let mut changed = false;
+            for &id in &object_ids {
+                if given_objects.contains(&id) {
+                    continue;
+                }
+                let level = self.object_tags_level(
+                    self.objects.get(&id).unwrap().tags.iter(),
+                    &object_levels,
+                    &morphism_levels,
+                );
+                changed |= object_levels.insert(id, level) != Some(level);
+            }
+            for &id in &morphism_ids {
+                if given_morphisms.contains(&id) {
+                    continue;
+                }
+                let morphism = self.morphisms.get(&id).unwrap();
+                let level = morphism
+                    .connection
+                    .end_points()
+                    .into_iter()
+                    .map(|object| object_levels.get(object).copied().unwrap_or(1))
+                    .chain(std::iter::once(self.morphism_tags_level(
+                        morphism.tags.iter(),
+                        &object_levels,
+                        &morphism_levels,
+                    )))
+                    .max()
+                    .unwrap_or(1);
+                changed |= morphism_levels.insert(id, level) != Some(level);
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        let mut constructions: BTreeMap<usize, Constraints<L>> = BTreeMap::new();
+        constructions.insert(0, Vec::new());
+        for &id in &object_ids {
+            let object = self.objects.get(&id).unwrap();
+            let tags: Vec<_> = if given_objects.contains(&id) {
+                // Tags referring to new things are introduced later
+                let (old, new): (Vec<_>, Vec<_>) = object.tags.iter().partition(|tag| {
+                    tag_is_given(tag.references(), &object_levels, &morphism_levels)
+                });
+                for tag in new {
+                    let level = self.object_tags_level(
+                        std::iter::once(tag),
+                        &object_levels,
+                        &morphism_levels,
+                    );
+                    constructions
+                        .entry(level)
+                        .or_default()
+                        .push(Constraint::Object {
+                            label: object_label(id),
+                            tags: vec![
+                                tag.map_borrowed(|&o| object_label(o), |&m| morphism_label(m))
+                            ],
+                        });
+                }
+                old
+            } else {
+                object.tags.iter().collect()
+            };
+            constructions
+                .entry(object_levels[&id])
+                .or_default()
+                .push(Constraint::Object {
+                    label: object_label(id),
+                    tags: tags
+                        .into_iter()
+                        .map(|tag| tag.map_borrowed(|&o| object_label(o), |&m| morphism_label(m)))
+                        .collect(),
+                });
+        }
+        for &id in &morphism_ids {
+            let morphism = self.morphisms.get(&id).unwrap();
+            let connection = match morphism.connection {
+                MorphismConnection::Regular { from, to } => MorphismConnection::Regular {
+                    from: object_label(from),
+                    to: object_label(to),
+                },
+                MorphismConnection::Isomorphism(a, b) => {
+                    MorphismConnection::Isomorphism(object_label(a), object_label(b))
+                }
+            };
+            let tags: Vec<_> = if given_morphisms.contains(&id) {
+                let (old, new): (Vec<_>, Vec<_>) = morphism.tags.iter().partition(|tag| {
+                    tag_is_given(tag.references(), &object_levels, &morphism_levels)
+                });
+                for tag in new {
+                    let level = self.morphism_tags_level(
+                        std::iter::once(tag),
+                        &object_levels,
+                        &morphism_levels,
+                    );
+                    constructions
+                        .entry(level)
+                        .or_default()
+                        .push(Constraint::Morphism {
+                            label: morphism_label(id),
+                            connection: connection.clone(),
+                            tags: vec![
+                                tag.map_borrowed(|&o| object_label(o), |&m| morphism_label(m))
+                            ],
+                        });
+                }
+                old
+            } else {
+                morphism.tags.iter().collect()
+            };
+            constructions
+                .entry(morphism_levels[&id])
+                .or_default()
+                .push(Constraint::Morphism {
+                    label: morphism_label(id),
+                    connection,
+                    tags: tags
+                        .into_iter()
+                        .map(|tag| tag.map_borrowed(|&o| object_label(o), |&m| morphism_label(m)))
+                        .collect(),
+                });
+        }
+
+        let mut equalities: Vec<_> = self.equalities.iter_equalities().collect();
+        equalities.sort();
+        let last = constructions.keys().last().copied().unwrap_or(0).max(1);
+        for equality in equalities {
+            let left = equality
+                .left()
+                .iter()
+                .map(|&id| morphism_label(id))
+                .collect();
+            let right = equality
+                .right()
+                .iter()
+                .map(|&id| morphism_label(id))
+                .collect();
+            let equality = Equality::new(left, right).expect("Equalities are expected to be valid");
+            constructions
+                .entry(last)
+                .or_default()
+                .push(Constraint::Equality(equality));
+        }
+
+        let mut statement = Vec::new();
+        for (level, constraints) in constructions {
+            if level == 0 {
+                if !constraints.is_empty() {
+                    statement.push(RuleConstruction::Forall(constraints));
+                }
+            } else {
+                statement.push(RuleConstruction::Exists(constraints));
+            }
+        }
+        Rule::new(statement)
+    }
+
+    /// Returns the earliest construction where an object with the tags can be introduced.
+    fn object_tags_level<'a>(
+        &self,
+        tags: impl Iterator<Item = &'a ObjectTag>,
+        object_levels: &HashMap<ObjectId, usize>,
+        morphism_levels: &HashMap<MorphismId, usize>,
+    ) -> usize {
+        tags.flat_map(|tag| {
+            let (objects, morphisms) = tag.references();
+            objects
+                .into_iter()
+                .map(|object| object_levels.get(object).copied().unwrap_or(1))
+                .chain(
+                    morphisms
+                        .into_iter()
+                        .map(|morphism| morphism_levels.get(morphism).copied().unwrap_or(1)),
+                )
+                .collect::<Vec<_>>()
+        })
+        .max()
+        .map_or(1, |level| level + 1)
+    }
+
+    /// Returns the earliest construction where a morphism with the tags can be introduced.
+    fn morphism_tags_level<'a>(
+        &self,
+        tags: impl Iterator<Item = &'a MorphismTag>,
+        object_levels: &HashMap<ObjectId, usize>,
+        morphism_levels: &HashMap<MorphismId, usize>,
+    ) -> usize {
+        tags.flat_map(|tag| {
+            let (objects, morphisms) = tag.references();
+            objects
+                .into_iter()
+                .map(|object| object_levels.get(object).copied().unwrap_or(1))
+                .chain(
+                    morphisms
+                        .into_iter()
+                        .map(|morphism| morphism_levels.get(morphism).copied().unwrap_or(1) + 1),
+                )
+                .collect::<Vec<_>>()
+        })
+        .max()
+        .unwrap_or(1)
+        .max(1)
+    }
+}
+
+/// Whether all the references are given.
+fn tag_is_given(
+    (objects, morphisms): (Vec<&ObjectId>, Vec<&MorphismId>),
+    object_levels: &HashMap<ObjectId, usize>,
+    morphism_levels: &HashMap<MorphismId, usize>,
+) -> bool {
+    objects
+        .into_iter()
+        .all(|object| object_levels.get(object) == Some(&0))
+        && morphisms
+            .into_iter()
+            .all(|morphism| morphism_levels.get(morphism) == Some(&0))
+}
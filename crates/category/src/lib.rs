@@ -2,23 +2,51 @@ mod action;
 mod bindings;
 mod builder;
 mod category;
+mod countermodel;
 mod equalities;
+mod equality_proof;
+mod finset;
+pub mod format;
+mod functor;
 mod label;
+mod lemma;
 mod morphism;
+mod natural;
 mod object;
+mod opposite;
+mod provenance;
+mod prover;
+mod retract;
 pub mod rule;
+mod saturate;
+mod script;
+mod solver;
 mod tag;
+mod validate;
 
 use self::category::*;
 pub use action::*;
 pub use bindings::*;
 pub use builder::*;
 pub use constraint::util;
+pub use countermodel::*;
 pub use equalities::*;
+pub use equality_proof::*;
+pub use finset::*;
+pub use functor::*;
 use label::*;
 use morphism::*;
+pub use natural::*;
 use object::*;
+pub use provenance::*;
+pub use prover::*;
+pub use retract::*;
 pub use rule::*;
+pub use saturate::*;
+pub use script::*;
+use serde::{Deserialize, Serialize};
+pub use solver::*;
+pub use validate::*;
 
 pub mod types {
     pub use crate::category::*;
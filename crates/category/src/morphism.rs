@@ -1,15 +1,35 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use super::*;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Morphism<T> {
     pub connection: MorphismConnection,
     pub tags: Vec<MorphismTag>,
     pub inner: T,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Mutable access to a morphism that keeps its connection unchanged,
+/// so that the endpoint indexes of [`Morphisms`] stay valid.
+/// To change the connection, replace the morphism with [`Category::insert_morphism`].
+#[derive(Debug)]
+pub struct MorphismMut<'a, T> {
+    pub connection: MorphismConnection,
+    pub tags: &'a mut Vec<MorphismTag>,
+    pub inner: &'a mut T,
+}
+
+impl<'a, T> MorphismMut<'a, T> {
+    fn new(morphism: &'a mut Morphism<T>) -> Self {
+        Self {
+            connection: morphism.connection,
+            tags: &mut morphism.tags,
+            inner: &mut morphism.inner,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum MorphismTag<O = ObjectId, M = MorphismId> {
     Identity(O),
     Unique,
@@ -17,9 +37,18 @@ pub enum MorphismTag<O = ObjectId, M = MorphismId> {
     Isomorphism(M, M),
     ProductP1,
     ProductP2,
+    CoproductJ1,
+    CoproductJ2,
+    EqualizerInclusion,
+    CoequalizerProjection,
+    PullbackP1,
+    PullbackP2,
+    PushoutJ1,
+    PushoutJ2,
+    Evaluation,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum MorphismConnection<T = ObjectId> {
     Regular { from: T, to: T },
     Isomorphism(T, T),
@@ -61,14 +90,31 @@ impl<T> MorphismConnection<T> {
     {
         self.end_points().iter().any(|object| **object == id)
     }
+
+    /// Returns the connection in the opposite category.
+    pub fn dual(self) -> Self {
+        match self {
+            Self::Regular { from, to } => Self::Regular { from: to, to: from },
+            Self::Isomorphism(a, b) => Self::Isomorphism(a, b),
+        }
+    }
 }
 
+/// A collection of morphisms, indexed by their endpoints.
+/// Isomorphisms are indexed by their endpoints in the order they are stored.
+#[derive(Clone)]
 pub struct Morphisms<T> {
     morphisms: HashMap<MorphismId, Morphism<T>>,
     next_id: MorphismId,
+    /// Morphisms by their first endpoint.
+    sources: HashMap<ObjectId, HashSet<MorphismId>>,
+    /// Morphisms by their second endpoint.
+    targets: HashMap<ObjectId, HashSet<MorphismId>>,
+    /// Morphisms by both of their endpoints.
+    pairs: HashMap<(ObjectId, ObjectId), HashSet<MorphismId>>,
 }
 
-#[derive(Hash, PartialEq, Eq, Debug, Clone, Copy, PartialOrd, Ord)]
+#[derive(Hash, PartialEq, Eq, Debug, Clone, Copy, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct MorphismId(u64);
 
 impl MorphismId {
@@ -77,17 +123,75 @@ impl MorphismId {
     }
 }
 
+#[derive(Serialize, Deserialize)]
+struct MorphismsRepr<T> {
+    next_id: MorphismId,
+    morphisms: Vec<(MorphismId, T)>,
+}
+
+impl<T: Serialize> Serialize for Morphisms<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // Sort by id to keep the output stable
+        let mut morphisms: Vec<_> = self
+            .morphisms
+            .iter()
+            .map(|(&id, morphism)| (id, morphism))
+            .collect();
+        morphisms.sort_by_key(|&(id, _)| id);
+        MorphismsRepr {
+            next_id: self.next_id,
+            morphisms,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Morphisms<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = MorphismsRepr::<Morphism<T>>::deserialize(deserializer)?;
+        let mut morphisms = Self {
+            morphisms: HashMap::with_capacity(repr.morphisms.len()),
+            next_id: repr.next_id,
+            sources: HashMap::new(),
+            targets: HashMap::new(),
+            pairs: HashMap::new(),
+        };
+        for (id, morphism) in repr.morphisms {
+            match morphisms.insert(morphism, id) {
+                Ok(None) => (),
+                Ok(Some(_)) => {
+                    return Err(serde::de::Error::custom(format!(
+                        "duplicate morphism id {}",
+                        id.0
+                    )))
+                }
+                Err(()) => {
+                    return Err(serde::de::Error::custom(format!(
+                        "morphism id {} is not less than the next id {}",
+                        id.0, repr.next_id.0
+                    )))
+                }
+            }
+        }
+        Ok(morphisms)
+    }
+}
+
 impl<T> Morphisms<T> {
     pub fn new() -> Self {
         Self {
             morphisms: HashMap::new(),
             next_id: MorphismId(0),
+            sources: HashMap::new(),
+            targets: HashMap::new(),
+            pairs: HashMap::new(),
         }
     }
 
     pub(crate) fn new_morphism(&mut self, morphism: Morphism<T>) -> MorphismId {
         let id = self.next_id;
         self.next_id.0 += 1;
+        self.index(id, &morphism.connection);
         assert!(
             self.morphisms.insert(id, morphism).is_none(),
             "Failed to generate new edge"
@@ -95,6 +199,17 @@ impl<T> Morphisms<T> {
         id
     }
 
+    pub(crate) fn next_id(&self) -> MorphismId {
+        self.next_id
+    }
+
+    /// Makes new morphisms reuse the ids starting from `next_id`.
+    /// All morphisms with those ids must have been removed.
+    pub(crate) fn rewind_next_id(&mut self, next_id: MorphismId) {
+        debug_assert!(self.morphisms.keys().all(|id| id.0 < next_id.0));
+        self.next_id = next_id;
+    }
+
     pub(crate) fn insert(
         &mut self,
         morphism: Morphism<T>,
@@ -104,7 +219,13 @@ impl<T> Morphisms<T> {
             return Err(());
         }
 
-        Ok(self.morphisms.insert(id, morphism))
+        let connection = morphism.connection;
+        let replaced = self.morphisms.insert(id, morphism);
+        if let Some(replaced) = &replaced {
+            self.unindex(id, &replaced.connection);
+        }
+        self.index(id, &connection);
+        Ok(replaced)
     }
 
     pub fn len(&self) -> usize {
@@ -115,27 +236,91 @@ impl<T> Morphisms<T> {
         self.morphisms.iter()
     }
 
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&MorphismId, &mut Morphism<T>)> {
-        self.morphisms.iter_mut()
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&MorphismId, MorphismMut<'_, T>)> {
+        self.morphisms
+            .iter_mut()
+            .map(|(id, morphism)| (id, MorphismMut::new(morphism)))
     }
 
     pub fn remove(&mut self, id: &MorphismId) -> Option<Morphism<T>> {
-        self.morphisms.remove(id)
+        let morphism = self.morphisms.remove(id)?;
+        self.unindex(*id, &morphism.connection);
+        Some(morphism)
     }
 
-    pub fn retain(&mut self, f: impl FnMut(&MorphismId, &mut Morphism<T>) -> bool) {
-        self.morphisms.retain(f);
+    pub fn retain(&mut self, mut f: impl FnMut(&MorphismId, MorphismMut<'_, T>) -> bool) {
+        let mut removed = Vec::new();
+        self.morphisms.retain(|id, morphism| {
+            let keep = f(id, MorphismMut::new(morphism));
+            if !keep {
+                removed.push((*id, morphism.connection));
+            }
+            keep
+        });
+        for (id, connection) in removed {
+            self.unindex(id, &connection);
+        }
     }
 
     pub fn get(&self, id: &MorphismId) -> Option<&Morphism<T>> {
         self.morphisms.get(id)
     }
 
-    pub fn get_mut(&mut self, id: &MorphismId) -> Option<&mut Morphism<T>> {
-        self.morphisms.get_mut(id)
+    pub fn get_mut(&mut self, id: &MorphismId) -> Option<MorphismMut<'_, T>> {
+        self.morphisms.get_mut(id).map(MorphismMut::new)
     }
 
     pub fn contains(&self, id: &MorphismId) -> bool {
         self.morphisms.contains_key(id)
     }
+
+    /// Returns the morphisms going out of the object.
+    pub fn outgoing(&self, object: ObjectId) -> impl Iterator<Item = MorphismId> + '_ {
+        self.sources.get(&object).into_iter().flatten().copied()
+    }
+
+    /// Returns the morphisms coming into the object.
+    pub fn incoming(&self, object: ObjectId) -> impl Iterator<Item = MorphismId> + '_ {
+        self.targets.get(&object).into_iter().flatten().copied()
+    }
+
+    /// Returns the morphisms going from one object to another.
+    pub fn between(&self, from: ObjectId, to: ObjectId) -> impl Iterator<Item = MorphismId> + '_ {
+        self.pairs.get(&(from, to)).into_iter().flatten().copied()
+    }
+
+    /// Returns the morphisms connected to the object, each one exactly once.
+    pub fn connected(&self, object: ObjectId) -> impl Iterator<Item = MorphismId> + '_ {
+        let outgoing = self.sources.get(&object);
+        self.outgoing(object).chain(
+            self.incoming(object)
+                .filter(move |id| !outgoing.is_some_and(|ids| ids.contains(id))),
+        )
+    }
+
+    fn index(&mut self, id: MorphismId, connection: &MorphismConnection) {
+        let [&from, &to] = connection.end_points();
+        self.sources.entry(from).or_default().insert(id);
+        self.targets.entry(to).or_default().insert(id);
+        self.pairs.entry((from, to)).or_default().insert(id);
+    }
+
+    fn unindex(&mut self, id: MorphismId, connection: &MorphismConnection) {
+        let [&from, &to] = connection.end_points();
+        fn remove<K: std::hash::Hash + Eq>(
+            index: &mut HashMap<K, HashSet<MorphismId>>,
+            key: K,
+            id: MorphismId,
+        ) {
+            if let Some(ids) = index.get_mut(&key) {
+                ids.remove(&id);
+                if ids.is_empty() {
+                    index.remove(&key);
+                }
+            }
+        }
+        remove(&mut self.sources, from, id);
+        remove(&mut self.targets, to, id);
+        remove(&mut self.pairs, (from, to), id);
+    }
 }
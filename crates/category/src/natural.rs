@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+
+use super::*;
+
+/// A family of component morphisms between the images of two functors,
+/// one for each object of the source category.
+#[derive(Debug, Clone)]
+pub struct NaturalTransformation {
+    components: HashMap<ObjectId, MorphismId>,
+}
+
+/// The result of checking a naturality square.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Commutativity {
+    /// The square is proven to commute.
+    Commutes,
+    /// The solver proved that the paths around the square are different.
+    Refuted,
+    /// The solver ran out of budget before deciding the square,
+    /// or the square passes through an isomorphism.
+    Unknown,
+}
+
+/// A naturality square that cannot be formed from the transformation and the functors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NaturalityError {
+    /// The object from the source category has no component.
+    MissingComponent(ObjectId),
+    /// The object from the source category is not mapped by one of the functors.
+    UnmappedObject(ObjectId),
+    /// The morphism from the source category is not mapped by one of the functors.
+    UnmappedMorphism(MorphismId),
+    /// The morphism is mapped to a morphism that does not exist in the target category.
+    UnknownMorphism {
+        morphism: MorphismId,
+        image: MorphismId,
+    },
+    /// The component does not exist in the target category,
+    /// or does not lead from the image of the object under the `from` functor
+    /// to its image under the `to` functor.
+    Component {
+        object: ObjectId,
+        component: MorphismId,
+    },
+}
+
+impl NaturalTransformation {
+    pub fn new() -> Self {
+        Self {
+            components: HashMap::new(),
+        }
+    }
+}
+
+impl Default for NaturalTransformation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NaturalTransformation {
+    pub fn set_component(&mut self, object: ObjectId, component: MorphismId) -> Option<MorphismId> {
+        self.components.insert(object, component)
+    }
+
+    pub fn get_component(&self, object: &ObjectId) -> Option<MorphismId> {
+        self.components.get(object).copied()
+    }
+
+    /// Checks the naturality square `G(f) ∘ α_X = α_Y ∘ F(f)`
+    /// for every regular morphism `f: X -> Y` of the source category,
+    /// where `F` is the `from` functor and `G` is the `to` functor.
+    /// The results are sorted by the morphism id.
+    #[allow(clippy::type_complexity)]
+    pub fn check<O, M, E, P, N, F>(
+        &self,
+        source: &Category<O, M, E>,
+        target: &Category<P, N, F>,
+        from: &Functor,
+        to: &Functor,
+    ) -> Vec<(MorphismId, Result<Commutativity, NaturalityError>)> {
+        let solver = LazySolver::new(target);
+        let mut squares: Vec<_> = source
+            .morphisms
+            .iter()
+            .filter_map(|(&id, morphism)| match morphism.connection {
+                MorphismConnection::Regular { from: x, to: y } => Some((id, x, y)),
+                MorphismConnection::Isomorphism(_, _) => None,
+            })
+            .map(|(id, x, y)| (id, self.check_square(id, x, y, &solver, from, to)))
+            .collect();
+        squares.sort_by_key(|&(id, _)| id);
+        squares
+    }
+
+    fn check_square<P, N, F>(
+        &self,
+        morphism: MorphismId,
+        x: ObjectId,
+        y: ObjectId,
+        solver: &LazySolver<'_, P, N, F>,
+        from: &Functor,
+        to: &Functor,
+    ) -> Result<Commutativity, NaturalityError> {
+        let target = solver.category();
+        let component = |object: ObjectId| {
+            let component = self
+                .get_component(&object)
+                .ok_or(NaturalityError::MissingComponent(object))?;
+            let (image_from, image_to) = from
+                .get_object(&object)
+                .zip(to.get_object(&object))
+                .ok_or(NaturalityError::UnmappedObject(object))?;
+            let expected = MorphismConnection::Regular {
+                from: image_from,
+                to: image_to,
+            };
+            match target.morphisms.get(&component) {
+                Some(morphism) if morphism.connection == expected => Ok(component),
+                _ => Err(NaturalityError::Component { object, component }),
+            }
+        };
+        let image = |functor: &Functor| {
+            let image = functor
+                .get_morphism(&morphism)
+                .ok_or(NaturalityError::UnmappedMorphism(morphism))?;
+            if target.morphisms.contains(&image) {
+                Ok(image)
+            } else {
+                Err(NaturalityError::UnknownMorphism { morphism, image })
+            }
+        };
+
+        let alpha_x = component(x)?;
+        let alpha_y = component(y)?;
+        let from_f = image(from)?;
+        let to_f = image(to)?;
+
+        // The solver does not pass through isomorphisms
+        let is_regular = |id: &MorphismId| {
+            target.morphisms.get(id).is_some_and(|morphism| {
+                matches!(morphism.connection, MorphismConnection::Regular { .. })
+            })
+        };
+        if !is_regular(&from_f) || !is_regular(&to_f) {
+            return Ok(Commutativity::Unknown);
+        }
+
+        Ok(
+            match solver.get().solve(&[alpha_x, to_f], &[from_f, alpha_y]) {
+                PathEquality::Equal => Commutativity::Commutes,
+                PathEquality::NotEqual => Commutativity::Refuted,
+                PathEquality::Unknown => Commutativity::Unknown,
+            },
+        )
+    }
+}
@@ -1,24 +1,33 @@
 use std::collections::HashMap;
 
-#[derive(Debug, Clone)]
+use super::*;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Object<T> {
     pub tags: Vec<ObjectTag>,
     pub inner: T,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum ObjectTag<O = ObjectId> {
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ObjectTag<O = ObjectId, M = MorphismId> {
     Initial,
     Terminal,
     Product(O, O),
+    Coproduct(O, O),
+    Exponential(O, O),
+    Equalizer(M, M),
+    Coequalizer(M, M),
+    Pullback(M, M),
+    Pushout(M, M),
 }
 
+#[derive(Clone)]
 pub struct Objects<O> {
     objects: HashMap<ObjectId, Object<O>>,
     next_id: ObjectId,
 }
 
-#[derive(Hash, PartialOrd, Ord, PartialEq, Eq, Debug, Clone, Copy)]
+#[derive(Hash, PartialOrd, Ord, PartialEq, Eq, Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct ObjectId(u64);
 
 impl ObjectId {
@@ -27,6 +36,57 @@ impl ObjectId {
     }
 }
 
+#[derive(Serialize, Deserialize)]
+struct ObjectsRepr<T> {
+    next_id: ObjectId,
+    objects: Vec<(ObjectId, T)>,
+}
+
+impl<T: Serialize> Serialize for Objects<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // Sort by id to keep the output stable
+        let mut objects: Vec<_> = self
+            .objects
+            .iter()
+            .map(|(&id, object)| (id, object))
+            .collect();
+        objects.sort_by_key(|&(id, _)| id);
+        ObjectsRepr {
+            next_id: self.next_id,
+            objects,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Objects<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = ObjectsRepr::<Object<T>>::deserialize(deserializer)?;
+        let mut objects = Self {
+            objects: HashMap::with_capacity(repr.objects.len()),
+            next_id: repr.next_id,
+        };
+        for (id, object) in repr.objects {
+            match objects.insert(object, id) {
+                Ok(None) => (),
+                Ok(Some(_)) => {
+                    return Err(serde::de::Error::custom(format!(
+                        "duplicate object id {}",
+                        id.0
+                    )))
+                }
+                Err(()) => {
+                    return Err(serde::de::Error::custom(format!(
+                        "object id {} is not less than the next id {}",
+                        id.0, repr.next_id.0
+                    )))
+                }
+            }
+        }
+        Ok(objects)
+    }
+}
+
 impl<T> Objects<T> {
     pub fn new() -> Self {
         Self {
@@ -45,6 +105,17 @@ impl<T> Objects<T> {
         id
     }
 
+    pub(crate) fn next_id(&self) -> ObjectId {
+        self.next_id
+    }
+
+    /// Makes new objects reuse the ids starting from `next_id`.
+    /// All objects with those ids must have been removed.
+    pub(crate) fn rewind_next_id(&mut self, next_id: ObjectId) {
+        debug_assert!(self.objects.keys().all(|id| id.0 < next_id.0));
+        self.next_id = next_id;
+    }
+
     pub(crate) fn insert(
         &mut self,
         object: Object<T>,
@@ -0,0 +1,43 @@
+use super::*;
+
+impl<O: Clone, M: Clone, E: Clone> Category<O, M, E> {
+    /// Constructs the opposite category: every regular morphism is reversed,
+    /// and compositions (including equalities) are taken in the reverse order.
+    /// Object and morphism ids are preserved, and tags are replaced with their duals.
+    /// Returns `None` if a tag has no dual.
+    pub fn opposite(&self) -> Option<Self> {
+        let mut category = self.clone();
+
+        for (_, object) in category.objects.iter_mut() {
+            object.tags = std::mem::take(&mut object.tags)
+                .into_iter()
+                .map(|tag| tag.dual())
+                .collect::<Option<_>>()?;
+        }
+
+        // Reinsert the morphisms to keep the endpoint indexes valid
+        let ids: Vec<_> = category.morphisms.iter().map(|(&id, _)| id).collect();
+        for id in ids {
+            let mut morphism = category
+                .morphisms
+                .remove(&id)
+                .expect("Id is taken from the map");
+            morphism.connection = morphism.connection.dual();
+            morphism.tags = std::mem::take(&mut morphism.tags)
+                .into_iter()
+                .map(|tag| tag.dual())
+                .collect::<Option<_>>()?;
+            category
+                .morphisms
+                .insert(morphism, id)
+                .expect("Id is less than the next id");
+        }
+
+        let equalities: Vec<_> = category.equalities.drain().collect();
+        for (equality, inner) in equalities {
+            category.equalities.new_equality(equality.dual(), inner);
+        }
+
+        Some(category)
+    }
+}
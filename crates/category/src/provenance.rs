@@ -0,0 +1,178 @@
+use super::*;
+
+/// The derivations of everything added to a category by rule applications.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Provenance {
+    /// Sorted by the step.
+    derivations: Vec<Derivation>,
+}
+
+/// What a single rule application has added to the category.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Derivation {
+    /// The number of the rule application, counting from 0.
+    pub step: usize,
+    /// The rule that was applied, if it has an id.
+    pub rule: Option<RuleId>,
+    /// The bindings the rule was applied with, sorted by the label.
+    pub bindings: Vec<(String, CategoryThing)>,
+    pub objects: Vec<ObjectId>,
+    pub object_tags: Vec<(ObjectId, Vec<ObjectTag>)>,
+    pub morphisms: Vec<MorphismId>,
+    pub morphism_tags: Vec<(MorphismId, Vec<MorphismTag>)>,
+    pub equalities: Vec<Equality>,
+}
+
+impl Provenance {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn derivations(&self) -> &[Derivation] {
+        &self.derivations
+    }
+
+    /// The step of the next rule application.
+    pub fn next_step(&self) -> usize {
+        self.derivations
+            .last()
+            .map_or(0, |derivation| derivation.step + 1)
+    }
+
+    pub fn get(&self, step: usize) -> Option<&Derivation> {
+        self.derivations
+            .binary_search_by_key(&step, |derivation| derivation.step)
+            .ok()
+            .map(|index| &self.derivations[index])
+    }
+
+    /// Returns the derivation that has created the object.
+    pub fn object_origin(&self, id: ObjectId) -> Option<&Derivation> {
+        self.derivations
+            .iter()
+            .find(|derivation| derivation.objects.contains(&id))
+    }
+
+    /// Returns the derivations that have created or tagged the object, in order.
+    pub fn object(&self, id: ObjectId) -> impl Iterator<Item = &Derivation> {
+        self.derivations.iter().filter(move |derivation| {
+            derivation.objects.contains(&id)
+                || derivation
+                    .object_tags
+                    .iter()
+                    .any(|(object, _)| *object == id)
+        })
+    }
+
+    /// Returns the derivation that has created the morphism.
+    pub fn morphism_origin(&self, id: MorphismId) -> Option<&Derivation> {
+        self.derivations
+            .iter()
+            .find(|derivation| derivation.morphisms.contains(&id))
+    }
+
+    /// Returns the derivations that have created or tagged the morphism, in order.
+    pub fn morphism(&self, id: MorphismId) -> impl Iterator<Item = &Derivation> {
+        self.derivations.iter().filter(move |derivation| {
+            derivation.morphisms.contains(&id)
+                || derivation
+                    .morphism_tags
+                    .iter()
+                    .any(|(morphism, _)| *morphism == id)
+        })
+    }
+
+    /// Returns the derivation that has added the equality.
+    pub fn equality(&self, equality: &Equality) -> Option<&Derivation> {
+        self.derivations
+            .iter()
+            .find(|derivation| derivation.equalities.contains(equality))
+    }
+
+    fn insert(&mut self, derivation: Derivation) {
+        let index = self
+            .derivations
+            .partition_point(|other| other.step < derivation.step);
+        if self.derivations.get(index).map(|other| other.step) == Some(derivation.step) {
+            panic!("Cannot replace an existing derivation with another");
+        }
+        self.derivations.insert(index, derivation);
+    }
+
+    fn remove(&mut self, step: usize) -> Option<Derivation> {
+        self.derivations
+            .binary_search_by_key(&step, |derivation| derivation.step)
+            .ok()
+            .map(|index| self.derivations.remove(index))
+    }
+}
+
+impl Derivation {
+    /// Collects what the rule has added from the actions that undo its application.
+    pub(crate) fn new<L: Label, O, M, E>(
+        step: usize,
+        rule: &Rule<L>,
+        bindings: &Bindings<L>,
+        undo: &[Action<O, M, E>],
+    ) -> Self {
+        let mut bindings: Vec<_> = bindings
+            .objects
+            .iter()
+            .map(|(label, &id)| (label_name(label), CategoryThing::Object { id }))
+            .chain(
+                bindings
+                    .morphisms
+                    .iter()
+                    .map(|(label, &id)| (label_name(label), CategoryThing::Morphism { id })),
+            )
+            .collect();
+        bindings.sort();
+
+        let mut derivation = Self {
+            step,
+            rule: rule.id().cloned(),
+            bindings,
+            objects: Vec::new(),
+            object_tags: Vec::new(),
+            morphisms: Vec::new(),
+            morphism_tags: Vec::new(),
+            equalities: Vec::new(),
+        };
+        for action in undo {
+            match action {
+                Action::RemoveObjects(ids) => derivation.objects.extend(ids),
+                Action::RemoveObjectTags(tags) => derivation.object_tags.extend(tags.clone()),
+                Action::RemoveMorphisms(ids) => derivation.morphisms.extend(ids),
+                Action::RemoveMorphismTags(tags) => derivation.morphism_tags.extend(tags.clone()),
+                Action::RemoveEqualities(equalities) => {
+                    derivation.equalities.extend(equalities.iter().cloned())
+                }
+                _ => (),
+            }
+        }
+        derivation.objects.sort();
+        derivation.morphisms.sort();
+        derivation
+    }
+}
+
+impl<O, M, E> Category<O, M, E> {
+    pub(crate) fn new_derivation(&mut self, derivation: Derivation) -> usize {
+        let step = derivation.step;
+        self.provenance.insert(derivation);
+        step
+    }
+
+    pub(crate) fn remove_derivation(&mut self, step: usize) -> Option<Derivation> {
+        self.provenance.remove(step)
+    }
+}
+
+/// The debug form of the label, without the quotes around strings.
+fn label_name<L: Label>(label: &L) -> String {
+    let name = format!("{label:?}");
+    name.strip_prefix('"')
+        .and_then(|name| name.strip_suffix('"'))
+        .map(str::to_owned)
+        .unwrap_or(name)
+}
@@ -0,0 +1,271 @@
+use std::time::{Duration, Instant};
+
+use super::*;
+
+/// How [`prove`] explores the rule applications.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchStrategy {
+    /// Iterative deepening: the shortest proofs are found first.
+    BreadthFirst,
+    /// Depth-first search that tries the applications
+    /// satisfying the most goal objects and morphisms first.
+    Heuristic,
+}
+
+/// The limits of the proof search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofBudget {
+    /// The maximum number of steps in a proof.
+    pub max_depth: usize,
+    /// The maximum number of rule applications tried during the search.
+    pub max_applications: usize,
+    pub time_limit: Option<Duration>,
+}
+
+/// A rule, given by its index, applied with the bindings.
+#[derive(Debug, Clone)]
+pub struct RuleApplication<L: Label> {
+    pub rule: usize,
+    pub bindings: Bindings<L>,
+}
+
+#[derive(Debug, Clone)]
+pub enum ProofOutcome<L: Label> {
+    /// The goal is reached by applying the rules in order.
+    Proved(Vec<RuleApplication<L>>),
+    /// No proof of at most `max_depth` steps exists.
+    NotFound,
+    /// The search ran out of applications or time.
+    OutOfBudget,
+}
+
+/// Searches for a sequence of rule applications that turns the fact category
+/// into one containing the goal, i.e. one where `goal.to_constraints()` has a candidate
+/// extending the `bindings`.
+///
+/// The fact category is modified during the search,
+/// but is restored to its original state before returning.
+/// Rules that start with a `forall` are tried with every binding of their input,
+/// and rules that start with an `exists` are tried once.
+#[allow(clippy::too_many_arguments)]
+pub fn prove<O, M, E, L: Label>(
+    fact: &mut Category<O, M, E>,
+    goal: &Category<O, M, E>,
+    bindings: &Bindings<CategoryThing>,
+    rules: &[Rule<L>],
+    strategy: SearchStrategy,
+    budget: &ProofBudget,
+    object_constructor: impl Fn(Vec<ObjectTag<&Object<O>, &Morphism<M>>>) -> O,
+    morphism_constructor: impl Fn(
+        MorphismConnection<&Object<O>>,
+        Vec<MorphismTag<&Object<O>, &Morphism<M>>>,
+    ) -> M,
+    equality_constructor: impl Fn(&Equality) -> E,
+) -> Result<ProofOutcome<L>, ApplyError<L>> {
+    let mut search = Search {
+        goal: goal.to_constraints(),
+        bindings,
+        rules,
+        strategy,
+        budget,
+        start: Instant::now(),
+        applications: 0,
+        cut: false,
+        steps: Vec::new(),
+        object_constructor,
+        morphism_constructor,
+        equality_constructor,
+    };
+
+    let depths = match strategy {
+        SearchStrategy::BreadthFirst => 0..=budget.max_depth,
+        SearchStrategy::Heuristic => budget.max_depth..=budget.max_depth,
+    };
+    for depth in depths {
+        search.cut = false;
+        match search.search(fact, depth)? {
+            Visit::Proved => return Ok(ProofOutcome::Proved(search.steps)),
+            Visit::OutOfBudget => return Ok(ProofOutcome::OutOfBudget),
+            // No branch reached the depth limit, so going deeper does not help
+            Visit::NotFound if !search.cut => break,
+            Visit::NotFound => (),
+        }
+    }
+    Ok(ProofOutcome::NotFound)
+}
+
+impl<O, M, E> Category<O, M, E> {
+    /// Returns every way to apply the rules to the category:
+    /// rules that start with a `forall` with every binding of their input,
+    /// and rules that start with an `exists` once.
+    pub(crate) fn rule_applications<L: Label>(
+        &self,
+        rules: &[Rule<L>],
+    ) -> Result<Vec<RuleApplication<L>>, ApplyError<L>> {
+        let mut applications = Vec::new();
+        for (index, rule) in rules.iter().enumerate() {
+            let bindings = match rule.get_statement().first() {
+                Some(RuleConstruction::Forall(constraints)) => self
+                    .find_candidates(constraints, &Bindings::new())?
+                    .map(|candidates| candidates.collect())
+                    .unwrap_or_else(|| vec![Bindings::new()]),
+                _ => vec![Bindings::new()],
+            };
+            applications.extend(bindings.into_iter().map(|bindings| RuleApplication {
+                rule: index,
+                bindings,
+            }));
+        }
+        Ok(applications)
+    }
+}
+
+enum Visit {
+    Proved,
+    NotFound,
+    OutOfBudget,
+}
+
+struct Search<'a, L: Label, FO, FM, FE> {
+    goal: Constraints<CategoryThing>,
+    bindings: &'a Bindings<CategoryThing>,
+    rules: &'a [Rule<L>],
+    strategy: SearchStrategy,
+    budget: &'a ProofBudget,
+    start: Instant,
+    applications: usize,
+    /// Whether the depth limit has cut off some branch.
+    cut: bool,
+    steps: Vec<RuleApplication<L>>,
+    object_constructor: FO,
+    morphism_constructor: FM,
+    equality_constructor: FE,
+}
+
+impl<'a, L: Label, FO, FM, FE> Search<'a, L, FO, FM, FE> {
+    fn search<O, M, E>(
+        &mut self,
+        category: &mut Category<O, M, E>,
+        depth: usize,
+    ) -> Result<Visit, ApplyError<L>>
+    where
+        FO: Fn(Vec<ObjectTag<&Object<O>, &Morphism<M>>>) -> O,
+        FM: Fn(MorphismConnection<&Object<O>>, Vec<MorphismTag<&Object<O>, &Morphism<M>>>) -> M,
+        FE: Fn(&Equality) -> E,
+    {
+        if self.is_goal_reached(category) {
+            return Ok(Visit::Proved);
+        }
+        if depth == 0 {
+            self.cut = true;
+            return Ok(Visit::NotFound);
+        }
+
+        let mut applications = category.rule_applications(self.rules)?;
+        if let SearchStrategy::Heuristic = self.strategy {
+            let mut scored = Vec::with_capacity(applications.len());
+            for application in applications {
+                let Some(score) = self.try_apply(category, &application, |search, category| {
+                    Ok(search.score(category))
+                })?
+                else {
+                    return Ok(Visit::OutOfBudget);
+                };
+                if let Some(score) = score {
+                    scored.push((score, application));
+                }
+            }
+            // Stable, so ties keep the rule order
+            scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+            applications = scored
+                .into_iter()
+                .map(|(_, application)| application)
+                .collect();
+        }
+
+        for application in applications {
+            let visit = self.try_apply(category, &application, |search, category| {
+                search.steps.push(application.clone());
+                let visit = search.search(category, depth - 1)?;
+                if !matches!(visit, Visit::Proved) {
+                    search.steps.pop();
+                }
+                Ok(visit)
+            })?;
+            match visit {
+                None => return Ok(Visit::OutOfBudget),
+                Some(None | Some(Visit::NotFound)) => (),
+                Some(Some(visit)) => return Ok(visit),
+            }
+        }
+        Ok(Visit::NotFound)
+    }
+
+    /// Applies the rule, calls `f` if the category has changed, and undoes the changes.
+    /// Returns `None` if the budget has run out.
+    fn try_apply<O, M, E, T>(
+        &mut self,
+        category: &mut Category<O, M, E>,
+        application: &RuleApplication<L>,
+        f: impl FnOnce(&mut Self, &mut Category<O, M, E>) -> Result<T, ApplyError<L>>,
+    ) -> Result<Option<Option<T>>, ApplyError<L>>
+    where
+        FO: Fn(Vec<ObjectTag<&Object<O>, &Morphism<M>>>) -> O,
+        FM: Fn(MorphismConnection<&Object<O>>, Vec<MorphismTag<&Object<O>, &Morphism<M>>>) -> M,
+        FE: Fn(&Equality) -> E,
+    {
+        if self.applications >= self.budget.max_applications
+            || self
+                .budget
+                .time_limit
+                .is_some_and(|limit| self.start.elapsed() >= limit)
+        {
+            return Ok(None);
+        }
+        self.applications += 1;
+
+        // Rewind the ids, so that the recorded bindings stay valid
+        // when the proof is replayed on the original category
+        let next_ids = (category.objects.next_id(), category.morphisms.next_id());
+        let (undo, applied) = category.apply_rule(
+            &self.rules[application.rule],
+            application.bindings.clone(),
+            &self.object_constructor,
+            &self.morphism_constructor,
+            &self.equality_constructor,
+        )?;
+        let result = if applied && !undo.is_empty() {
+            f(self, category).map(Some)
+        } else {
+            Ok(None)
+        };
+        category.action_undo(undo);
+        category.objects.rewind_next_id(next_ids.0);
+        category.morphisms.rewind_next_id(next_ids.1);
+        result.map(Some)
+    }
+
+    fn is_goal_reached<O, M, E>(&self, category: &Category<O, M, E>) -> bool {
+        match category.find_candidates(&self.goal, self.bindings) {
+            Ok(Some(mut candidates)) => candidates.next().is_some(),
+            // An empty goal is always reached
+            Ok(None) => true,
+            Err(_) => false,
+        }
+    }
+
+    /// The number of goal objects and morphisms that have a candidate on their own.
+    fn score<O, M, E>(&self, category: &Category<O, M, E>) -> usize {
+        self.goal
+            .iter()
+            .filter(|constraint| !matches!(constraint, Constraint::Equality(_)))
+            .filter(|constraint| {
+                category
+                    .find_candidates(std::slice::from_ref(*constraint), self.bindings)
+                    .ok()
+                    .flatten()
+                    .is_some_and(|mut candidates| candidates.next().is_some())
+            })
+            .count()
+    }
+}
@@ -0,0 +1,188 @@
+use std::collections::BTreeSet;
+
+use super::*;
+
+/// Everything that has to go when some facts are retracted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Retraction {
+    pub objects: Vec<ObjectId>,
+    pub morphisms: Vec<MorphismId>,
+    /// Equalities that are removed on their own,
+    /// not counting those that mention the removed morphisms.
+    pub equalities: Vec<Equality>,
+    /// Tags removed from the objects that stay.
+    pub object_tags: Vec<(ObjectId, Vec<ObjectTag>)>,
+    /// Tags removed from the morphisms that stay.
+    pub morphism_tags: Vec<(MorphismId, Vec<MorphismTag>)>,
+}
+
+impl<O, M, E> Category<O, M, E> {
+    /// Collects the things together with their transitive consequences:
+    /// - morphisms whose end points are removed,
+    /// - objects and morphisms whose tags refer to something removed,
+    /// - everything added by a rule applied with something removed among its bindings.
+    ///
+    /// Equalities mentioning the removed morphisms are not listed,
+    /// since they are removed together with the morphisms.
+    pub fn consequences(&self, things: impl IntoIterator<Item = CategoryThing>) -> Retraction {
+        let mut objects = BTreeSet::new();
+        let mut morphisms = BTreeSet::new();
+        for thing in things {
+            match thing {
+                CategoryThing::Object { id } if self.objects.contains(&id) => {
+                    objects.insert(id);
+                }
+                CategoryThing::Morphism { id } if self.morphisms.contains(&id) => {
+                    morphisms.insert(id);
+                }
+                _ => (),
+            }
+        }
+        let mut equalities = BTreeSet::new();
+
+        let is_removed =
+            |objects: &BTreeSet<ObjectId>,
+             morphisms: &BTreeSet<MorphismId>,
+             (object_refs, morphism_refs): (Vec<&ObjectId>, Vec<&MorphismId>)| {
+                object_refs.into_iter().any(|id| objects.contains(id))
+                    || morphism_refs.into_iter().any(|id| morphisms.contains(id))
+            };
+
+        loop {
+            let mut changed = false;
+
+            for (&id, object) in self.objects.iter() {
+                if !objects.contains(&id)
+                    && object
+                        .tags
+                        .iter()
+                        .any(|tag| is_removed(&objects, &morphisms, tag.references()))
+                {
+                    objects.insert(id);
+                    changed = true;
+                }
+            }
+            for (&id, morphism) in self.morphisms.iter() {
+                if !morphisms.contains(&id)
+                    && (morphism
+                        .connection
+                        .end_points()
+                        .into_iter()
+                        .any(|object| objects.contains(object))
+                        || morphism
+                            .tags
+                            .iter()
+                            .any(|tag| is_removed(&objects, &morphisms, tag.references())))
+                {
+                    morphisms.insert(id);
+                    changed = true;
+                }
+            }
+
+            for derivation in self.provenance.derivations() {
+                if !depends_on(derivation, &objects, &morphisms) {
+                    continue;
+                }
+                for &id in &derivation.objects {
+                    if self.objects.contains(&id) {
+                        changed |= objects.insert(id);
+                    }
+                }
+                for &id in &derivation.morphisms {
+                    if self.morphisms.contains(&id) {
+                        changed |= morphisms.insert(id);
+                    }
+                }
+                for equality in &derivation.equalities {
+                    if self.equalities.contains_equality(equality) {
+                        equalities.insert(equality.clone());
+                    }
+                }
+            }
+
+            if !changed {
+                break;
+            }
+        }
+
+        // Tags of the remaining things added by the retracted rule applications
+        let mut object_tags: Vec<(ObjectId, Vec<ObjectTag>)> = Vec::new();
+        let mut morphism_tags: Vec<(MorphismId, Vec<MorphismTag>)> = Vec::new();
+        for derivation in self.provenance.derivations() {
+            if !depends_on(derivation, &objects, &morphisms) {
+                continue;
+            }
+            for (id, tags) in &derivation.object_tags {
+                if !objects.contains(id) && self.objects.contains(id) {
+                    object_tags.push((*id, tags.clone()));
+                }
+            }
+            for (id, tags) in &derivation.morphism_tags {
+                if !morphisms.contains(id) && self.morphisms.contains(id) {
+                    morphism_tags.push((*id, tags.clone()));
+                }
+            }
+        }
+
+        // Equalities that go with the morphisms anyway
+        equalities.retain(|equality| {
+            !equality
+                .left()
+                .iter()
+                .chain(equality.right())
+                .any(|id| morphisms.contains(id))
+        });
+
+        Retraction {
+            objects: objects.into_iter().collect(),
+            morphisms: morphisms.into_iter().collect(),
+            equalities: equalities.into_iter().collect(),
+            object_tags,
+            morphism_tags,
+        }
+    }
+
+    /// Removes the things together with their [consequences](Category::consequences).
+    /// Returns the actions that undo the retraction.
+    pub fn retract(
+        &mut self,
+        things: impl IntoIterator<Item = CategoryThing>,
+    ) -> Vec<Action<O, M, E>> {
+        let retraction = self.consequences(things);
+        let mut actions = Vec::new();
+        if !retraction.equalities.is_empty() {
+            actions.push(Action::RemoveEqualities(retraction.equalities));
+        }
+        if !retraction.object_tags.is_empty() {
+            actions.push(Action::RemoveObjectTags(retraction.object_tags));
+        }
+        if !retraction.morphism_tags.is_empty() {
+            actions.push(Action::RemoveMorphismTags(retraction.morphism_tags));
+        }
+        if !retraction.morphisms.is_empty() {
+            actions.push(Action::RemoveMorphisms(retraction.morphisms));
+        }
+        if !retraction.objects.is_empty() {
+            actions.push(Action::RemoveObjects(retraction.objects));
+        }
+
+        let mut undo = Vec::new();
+        for action in actions {
+            // Later actions are undone first
+            undo.splice(0..0, self.action_do_unchecked(action));
+        }
+        undo
+    }
+}
+
+/// Whether the rule was applied with any of the things among its bindings.
+fn depends_on(
+    derivation: &Derivation,
+    objects: &BTreeSet<ObjectId>,
+    morphisms: &BTreeSet<MorphismId>,
+) -> bool {
+    derivation.bindings.iter().any(|(_, thing)| match thing {
+        CategoryThing::Object { id } => objects.contains(id),
+        CategoryThing::Morphism { id } => morphisms.contains(id),
+    })
+}
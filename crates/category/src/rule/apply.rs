@@ -1,126 +1,209 @@
 use super::*;
 
+/// An error that prevents a rule from being applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApplyError<L> {
+    /// The label is bound to an object that is not in the category.
+    UnknownObject { label: L, id: ObjectId },
+    /// The label is bound to a morphism that is not in the category.
+    UnknownMorphism { label: L, id: MorphismId },
+    /// The label is expected to be bound to an object, but it is not.
+    UnboundObject { label: L },
+    /// The label is expected to be bound to a morphism, but it is not.
+    UnboundMorphism { label: L },
+}
+
+/// The things an exists construction has created, given the bindings it was applied with.
+#[derive(Debug, Clone)]
+pub(crate) struct Construction<L: Label> {
+    pub bindings: Bindings<L>,
+    pub created: Bindings<L>,
+}
+
 impl<O, M, E> Category<O, M, E> {
+    /// Applies the rule to the category.
+    /// Returns the actions that undo the changes and whether the rule was applied.
+    /// If an error occurs, the category is left unchanged.
+    #[allow(clippy::type_complexity)]
     pub fn apply_rule<L: Label>(
         &mut self,
         rule: &Rule<L>,
         bindings: Bindings<L>,
-        object_constructor: impl Fn(Vec<ObjectTag<&Object<O>>>) -> O,
+        object_constructor: impl Fn(Vec<ObjectTag<&Object<O>, &Morphism<M>>>) -> O,
         morphism_constructor: impl Fn(
             MorphismConnection<&Object<O>>,
             Vec<MorphismTag<&Object<O>, &Morphism<M>>>,
         ) -> M,
         equality_constructor: impl Fn(&Equality) -> E,
-    ) -> (Vec<Action<O, M, E>>, bool) {
-        self.apply_impl(
+    ) -> Result<(Vec<Action<O, M, E>>, bool), ApplyError<L>> {
+        self.apply_rule_traced(
+            rule,
+            bindings,
+            object_constructor,
+            morphism_constructor,
+            equality_constructor,
+        )
+        .map(|(actions, applied, _)| (actions, applied))
+    }
+
+    /// Applies the rule like [`Category::apply_rule`],
+    /// and also returns what each exists construction has created, in order.
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn apply_rule_traced<L: Label>(
+        &mut self,
+        rule: &Rule<L>,
+        bindings: Bindings<L>,
+        object_constructor: impl Fn(Vec<ObjectTag<&Object<O>, &Morphism<M>>>) -> O,
+        morphism_constructor: impl Fn(
+            MorphismConnection<&Object<O>>,
+            Vec<MorphismTag<&Object<O>, &Morphism<M>>>,
+        ) -> M,
+        equality_constructor: impl Fn(&Equality) -> E,
+    ) -> Result<(Vec<Action<O, M, E>>, bool, Vec<Construction<L>>), ApplyError<L>> {
+        let mut actions = Vec::new();
+        let mut constructions = Vec::new();
+        let derivation_bindings = bindings.clone();
+        match self.apply_impl(
             rule.get_statement(),
             bindings,
+            &mut actions,
+            &mut constructions,
             &object_constructor,
             &morphism_constructor,
             &equality_constructor,
-        )
+        ) {
+            Ok(applied) => {
+                if !actions.is_empty() {
+                    let derivation = Derivation::new(
+                        self.provenance.next_step(),
+                        rule,
+                        &derivation_bindings,
+                        &actions,
+                    );
+                    actions.extend(self.action_do_unchecked(Action::NewDerivation(derivation)));
+                }
+                self.debug_validate();
+                Ok((actions, applied, constructions))
+            }
+            Err(error) => {
+                self.action_undo(actions);
+                Err(error)
+            }
+        }
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn apply_impl<L: Label>(
         &mut self,
         statement: &[RuleConstruction<L>],
         bindings: Bindings<L>,
-        object_constructor: &impl Fn(Vec<ObjectTag<&Object<O>>>) -> O,
+        actions: &mut Vec<Action<O, M, E>>,
+        constructions: &mut Vec<Construction<L>>,
+        object_constructor: &impl Fn(Vec<ObjectTag<&Object<O>, &Morphism<M>>>) -> O,
         morphism_constructor: &impl Fn(
             MorphismConnection<&Object<O>>,
             Vec<MorphismTag<&Object<O>, &Morphism<M>>>,
         ) -> M,
         equality_constructor: &impl Fn(&Equality) -> E,
-    ) -> (Vec<Action<O, M, E>>, bool) {
+    ) -> Result<bool, ApplyError<L>> {
         let construction = match statement.first() {
             Some(construction) => construction,
-            None => return (Vec::new(), false),
+            None => return Ok(false),
         };
 
         let statement = &statement[1..];
         match construction {
-            RuleConstruction::Forall(constraints) => self
-                .find_candidates(constraints, &bindings)
-                .map(|candidates| candidates.collect::<Vec<_>>())
-                .unwrap_or_else(|| vec![Bindings::new()])
-                .into_iter()
-                .map(|mut binds| {
+            RuleConstruction::Forall(constraints) => {
+                let candidates = self
+                    .find_candidates(constraints, &bindings)?
+                    .map(|candidates| candidates.collect::<Vec<_>>())
+                    .unwrap_or_else(|| vec![Bindings::new()]);
+
+                let mut applied = false;
+                for mut binds in candidates {
                     binds.extend(bindings.clone());
-                    self.apply_impl(
+                    applied |= self.apply_impl(
                         statement,
                         binds,
+                        actions,
+                        constructions,
                         object_constructor,
                         morphism_constructor,
                         equality_constructor,
-                    )
-                })
-                .fold(
-                    (Vec::new(), false),
-                    |(mut acc_actions, acc_apply), (action, apply)| {
-                        acc_actions.extend(action);
-                        (acc_actions, acc_apply || apply)
-                    },
-                ),
+                    )?;
+                }
+                Ok(applied)
+            }
             RuleConstruction::Exists(constraints) => {
                 let candidates = self
-                    .find_candidates(constraints, &bindings)
+                    .find_candidates(constraints, &bindings)?
                     .map(|candidates| candidates.collect::<Vec<_>>())
                     .unwrap_or_else(|| vec![Bindings::new()]);
 
                 if candidates.is_empty() {
-                    let (mut actions, new_binds) = self.apply_constraints(
+                    let (new_actions, new_binds) = self.apply_constraints(
                         constraints,
                         &bindings,
                         object_constructor,
                         morphism_constructor,
                         equality_constructor,
-                    );
-                    actions.extend(
+                    )?;
+                    actions.extend(new_actions);
+                    constructions.push(Construction {
+                        created: Bindings {
+                            objects: new_binds
+                                .objects
+                                .iter()
+                                .filter(|(label, _)| bindings.get_object(label).is_none())
+                                .map(|(label, &id)| (label.clone(), id))
+                                .collect(),
+                            morphisms: new_binds
+                                .morphisms
+                                .iter()
+                                .filter(|(label, _)| bindings.get_morphism(label).is_none())
+                                .map(|(label, &id)| (label.clone(), id))
+                                .collect(),
+                        },
+                        bindings,
+                    });
+                    self.apply_impl(
+                        statement,
+                        new_binds,
+                        actions,
+                        constructions,
+                        object_constructor,
+                        morphism_constructor,
+                        equality_constructor,
+                    )?;
+                    Ok(true)
+                } else {
+                    for mut binds in candidates {
+                        binds.extend(bindings.clone());
+                        // Keep object and morphism constraints to add extra tags
+                        let constraints =
+                            constraints.iter().filter(|constraint| match constraint {
+                                Constraint::Object { .. } | Constraint::Morphism { .. } => true,
+                                Constraint::Equality(_) => false,
+                            });
+                        let (new_actions, binds) = self.apply_constraints(
+                            constraints,
+                            &binds,
+                            object_constructor,
+                            morphism_constructor,
+                            equality_constructor,
+                        )?;
+                        actions.extend(new_actions);
                         self.apply_impl(
                             statement,
-                            new_binds,
+                            binds,
+                            actions,
+                            constructions,
                             object_constructor,
                             morphism_constructor,
                             equality_constructor,
-                        )
-                        .0,
-                    );
-                    (actions, true)
-                } else {
-                    candidates
-                        .into_iter()
-                        .map(|mut binds| {
-                            binds.extend(bindings.clone());
-                            // Keep object and morphism constraints to add extra tags
-                            let constraints =
-                                constraints.iter().filter(|constraint| match constraint {
-                                    Constraint::Object { .. } | Constraint::Morphism { .. } => true,
-                                    Constraint::Equality(_) => false,
-                                });
-                            let (mut actions, binds) = self.apply_constraints(
-                                constraints,
-                                &binds,
-                                object_constructor,
-                                morphism_constructor,
-                                equality_constructor,
-                            );
-                            let (new_actions, _) = self.apply_impl(
-                                statement,
-                                binds,
-                                object_constructor,
-                                morphism_constructor,
-                                equality_constructor,
-                            );
-                            actions.extend(new_actions);
-                            (actions, true)
-                        })
-                        .fold(
-                            (vec![], false),
-                            |(mut acc_actions, acc_apply), (action, apply)| {
-                                acc_actions.extend(action);
-                                (acc_actions, acc_apply || apply)
-                            },
-                        )
+                        )?;
+                    }
+                    Ok(true)
                 }
             }
         }
@@ -1,20 +1,9 @@
 use super::*;
 
-pub fn rule_composition<'a, T: Label + From<&'a str>>() -> Result<Rule<T>, RuleConstructionError> {
-    RuleBuilder::new()
-        .forall(
-            ConstraintsBuilder::new()
-                .morphism("f", "A", "B", vec![])
-                .morphism("g", "B", "C", vec![]),
-        )
-        .exists(ConstraintsBuilder::new().morphism(
-            "g o f",
-            "A",
-            "C",
-            vec![MorphismTag::Composition {
-                first: "f",
-                second: "g",
-            }],
-        ))
-        .build()
+pub fn rule_composition<'a, T: Label + From<&'a str>>() -> Result<Rule<T>, ParseError> {
+    Rule::parse(
+        r#"forall f: A -> B, g: B -> C.
+           exists "g o f": A -> C [composition(f, g)]."#,
+    )
+    .map(|rule| rule.named("composition"))
 }
@@ -0,0 +1,15 @@
+use super::*;
+
+pub fn rule_coproduct<'a, T: Label + From<&'a str>>() -> Result<Rule<T>, ParseError> {
+    Rule::parse(
+        "forall A, B.
+         exists A+B [coproduct(A, B)].
+         exists id: A+B -> A+B [identity(A+B)].
+         exists j1: A -> A+B [coproduct_j1], j2: B -> A+B [coproduct_j2].
+         forall C, f: A -> C, g: B -> C.
+         exists m: A+B -> C, m ∘ j1 = f, m ∘ j2 = g.
+         forall m': A+B -> C, m' ∘ j1 = f, m' ∘ j2 = g.
+         exists m = m'.",
+    )
+    .map(|rule| rule.named("coproduct"))
+}
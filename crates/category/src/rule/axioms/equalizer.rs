@@ -0,0 +1,29 @@
+use super::*;
+
+pub fn rule_equalizer<'a, T: Label + From<&'a str>>() -> Result<Rule<T>, ParseError> {
+    Rule::parse(
+        "forall f: A -> B, g: A -> B.
+         exists E [equalizer(f, g)].
+         exists id: E -> E [identity(E)].
+         exists e: E -> A [equalizer_inclusion], f ∘ e = g ∘ e.
+         forall X, h: X -> A, f ∘ h = g ∘ h.
+         exists u: X -> E, e ∘ u = h.
+         forall u': X -> E, e ∘ u' = h.
+         exists u = u'.",
+    )
+    .map(|rule| rule.named("equalizer"))
+}
+
+pub fn rule_coequalizer<'a, T: Label + From<&'a str>>() -> Result<Rule<T>, ParseError> {
+    Rule::parse(
+        "forall f: A -> B, g: A -> B.
+         exists Q [coequalizer(f, g)].
+         exists id: Q -> Q [identity(Q)].
+         exists q: B -> Q [coequalizer_projection], q ∘ f = q ∘ g.
+         forall X, h: B -> X, h ∘ f = h ∘ g.
+         exists u: Q -> X, u ∘ q = h.
+         forall u': Q -> X, u' ∘ q = h.
+         exists u = u'.",
+    )
+    .map(|rule| rule.named("coequalizer"))
+}
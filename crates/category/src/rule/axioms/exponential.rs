@@ -0,0 +1,21 @@
+use super::*;
+
+pub fn rule_exponential<'a, T: Label + From<&'a str>>() -> Result<Rule<T>, ParseError> {
+    Rule::parse(
+        r#"forall A, B.
+           exists B^A [exponential(B, A)].
+           exists id: B^A -> B^A [identity(B^A)].
+           exists B^AxA [product(B^A, A)].
+           exists id': B^AxA -> B^AxA [identity(B^AxA)].
+           exists p1: B^AxA -> B^A [product_p1], p2: B^AxA -> A [product_p2].
+           exists eval: B^AxA -> B [evaluation].
+           forall C, CxA [product(C, A)],
+              q1: CxA -> C [product_p1], q2: CxA -> A [product_p2], f: CxA -> B.
+           exists λf: C -> B^A, "λf x id": CxA -> B^AxA,
+              λf ∘ q1 = p1 ∘ "λf x id", p2 ∘ "λf x id" = q2, eval ∘ "λf x id" = f.
+           forall λf': C -> B^A, "λf' x id": CxA -> B^AxA,
+              λf' ∘ q1 = p1 ∘ "λf' x id", p2 ∘ "λf' x id" = q2, eval ∘ "λf' x id" = f.
+           exists λf = λf'."#,
+    )
+    .map(|rule| rule.named("exponential"))
+}
@@ -1,13 +1,9 @@
 use super::*;
 
-pub fn rule_identity<'a, T: Label + From<&'a str>>() -> Result<Rule<T>, RuleConstructionError> {
-    RuleBuilder::new()
-        .forall(ConstraintsBuilder::new().object("A", vec![]))
-        .exists(ConstraintsBuilder::new().morphism(
-            "id",
-            "A",
-            "A",
-            vec![MorphismTag::Identity("A")],
-        ))
-        .build()
+pub fn rule_identity<'a, T: Label + From<&'a str>>() -> Result<Rule<T>, ParseError> {
+    Rule::parse(
+        "forall A.
+         exists id: A -> A [identity(A)].",
+    )
+    .map(|rule| rule.named("identity"))
 }
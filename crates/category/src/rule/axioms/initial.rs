@@ -1,9 +1,10 @@
 use super::*;
 
-pub fn rule_initial<'a, T: Label + From<&'a str>>() -> Result<Rule<T>, RuleConstructionError> {
-    RuleBuilder::new()
-        .forall(ConstraintsBuilder::new().object("A", vec![]))
-        .exists(ConstraintsBuilder::new().object("0", vec![ObjectTag::Initial]))
-        .exists(ConstraintsBuilder::new().morphism("", "0", "A", vec![MorphismTag::Unique]))
-        .build()
+pub fn rule_initial<'a, T: Label + From<&'a str>>() -> Result<Rule<T>, ParseError> {
+    Rule::parse(
+        r#"forall A.
+           exists 0 [initial].
+           exists "": 0 -> A [unique]."#,
+    )
+    .map(|rule| rule.named("initial"))
 }
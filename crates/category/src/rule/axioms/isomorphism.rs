@@ -1,24 +1,11 @@
 use super::*;
 
-pub fn rule_isomorphism<'a, T: Label + From<&'a str>>() -> Result<Rule<T>, RuleConstructionError> {
-    RuleBuilder::new()
-        .forall(
-            ConstraintsBuilder::new()
-                .morphism("f", "A", "B", vec![])
-                .morphism("g", "B", "A", vec![]),
-        )
-        .forall(
-            ConstraintsBuilder::new()
-                .morphism("id_a", "A", "A", vec![MorphismTag::Identity("A")])
-                .morphism("id_b", "B", "B", vec![MorphismTag::Identity("B")])
-                .equality(vec!["f", "g"], vec!["id_a"])
-                .equality(vec!["g", "f"], vec!["id_b"]),
-        )
-        .exists(ConstraintsBuilder::new().isomorphism(
-            "",
-            "A",
-            "B",
-            vec![MorphismTag::Isomorphism("f", "g")],
-        ))
-        .build()
+pub fn rule_isomorphism<'a, T: Label + From<&'a str>>() -> Result<Rule<T>, ParseError> {
+    Rule::parse(
+        r#"forall f: A -> B, g: B -> A.
+           forall id_a: A -> A [identity(A)], id_b: B -> B [identity(B)],
+              g ∘ f = id_a, f ∘ g = id_b.
+           exists "": A <-> B [isomorphism(f, g)]."#,
+    )
+    .map(|rule| rule.named("isomorphism"))
 }
@@ -1,18 +1,27 @@
 mod composition;
+mod coproduct;
+mod equalizer;
+mod exponential;
 mod identity;
 mod initial;
 mod isomorphism;
 mod product;
+mod pullback;
 mod terminal;
 mod unique;
 
 use super::*;
+use text::ParseError;
 
 pub use composition::*;
+pub use coproduct::*;
+pub use equalizer::*;
+pub use exponential::*;
 pub use identity::*;
 pub use initial::*;
 pub use isomorphism::*;
 pub use product::*;
+pub use pullback::*;
 pub use terminal::*;
 pub use unique::*;
 
@@ -20,13 +29,19 @@ pub fn rule_axioms<'a, T: Label + From<&'a str>>() -> Vec<Rule<T>> {
     get_axioms().expect("Axioms are expected to be valid")
 }
 
-fn get_axioms<'a, T: Label + From<&'a str>>() -> Result<Vec<Rule<T>>, RuleConstructionError> {
+fn get_axioms<'a, T: Label + From<&'a str>>() -> Result<Vec<Rule<T>>, ParseError> {
     Ok(vec![
         rule_identity()?,
         rule_composition()?,
         rule_terminal()?,
-        // rule_initial()?,
+        rule_initial()?,
         rule_product()?,
+        rule_coproduct()?,
+        rule_equalizer()?,
+        rule_coequalizer()?,
+        rule_pullback()?,
+        rule_pushout()?,
+        rule_exponential()?,
         rule_unique()?,
         rule_isomorphism()?,
     ])
@@ -0,0 +1,29 @@
+use super::*;
+
+pub fn rule_pullback<'a, T: Label + From<&'a str>>() -> Result<Rule<T>, ParseError> {
+    Rule::parse(
+        "forall f: A -> C, g: B -> C.
+         exists P [pullback(f, g)].
+         exists id: P -> P [identity(P)].
+         exists p1: P -> A [pullback_p1], p2: P -> B [pullback_p2], f ∘ p1 = g ∘ p2.
+         forall X, h: X -> A, k: X -> B, f ∘ h = g ∘ k.
+         exists m: X -> P, p1 ∘ m = h, p2 ∘ m = k.
+         forall m': X -> P, p1 ∘ m' = h, p2 ∘ m' = k.
+         exists m = m'.",
+    )
+    .map(|rule| rule.named("pullback"))
+}
+
+pub fn rule_pushout<'a, T: Label + From<&'a str>>() -> Result<Rule<T>, ParseError> {
+    Rule::parse(
+        "forall f: C -> A, g: C -> B.
+         exists Q [pushout(f, g)].
+         exists id: Q -> Q [identity(Q)].
+         exists j1: A -> Q [pushout_j1], j2: B -> Q [pushout_j2], j1 ∘ f = j2 ∘ g.
+         forall X, h: A -> X, k: B -> X, h ∘ f = k ∘ g.
+         exists m: Q -> X, m ∘ j1 = h, m ∘ j2 = k.
+         forall m': Q -> X, m' ∘ j1 = h, m' ∘ j2 = k.
+         exists m = m'.",
+    )
+    .map(|rule| rule.named("pushout"))
+}
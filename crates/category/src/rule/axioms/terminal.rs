@@ -1,9 +1,10 @@
 use super::*;
 
-pub fn rule_terminal<'a, T: Label + From<&'a str>>() -> Result<Rule<T>, RuleConstructionError> {
-    RuleBuilder::new()
-        .forall(ConstraintsBuilder::new().object("A", vec![]))
-        .exists(ConstraintsBuilder::new().object("1", vec![ObjectTag::Terminal]))
-        .exists(ConstraintsBuilder::new().morphism("", "A", "1", vec![MorphismTag::Unique]))
-        .build()
+pub fn rule_terminal<'a, T: Label + From<&'a str>>() -> Result<Rule<T>, ParseError> {
+    Rule::parse(
+        r#"forall A.
+           exists 1 [terminal].
+           exists "": A -> 1 [unique]."#,
+    )
+    .map(|rule| rule.named("terminal"))
 }
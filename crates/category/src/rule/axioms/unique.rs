@@ -1,9 +1,10 @@
 use super::*;
 
-pub fn rule_unique<'a, T: Label + From<&'a str>>() -> Result<Rule<T>, RuleConstructionError> {
-    RuleBuilder::new()
-        .forall(ConstraintsBuilder::new().morphism("f", "A", "B", vec![]))
-        .forall(ConstraintsBuilder::new().morphism("m", "A", "B", vec![MorphismTag::Unique]))
-        .exists(ConstraintsBuilder::new().equality(vec!["f"], vec!["m"]))
-        .build()
+pub fn rule_unique<'a, T: Label + From<&'a str>>() -> Result<Rule<T>, ParseError> {
+    Rule::parse(
+        "forall f: A -> B.
+         forall m: A -> B [unique].
+         exists f = m.",
+    )
+    .map(|rule| rule.named("unique"))
 }
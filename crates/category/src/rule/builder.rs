@@ -2,11 +2,20 @@ use super::*;
 
 pub struct RuleBuilder<L: Label> {
     statement: RuleStatement<L>,
+    name: Option<String>,
 }
 
 impl<L: Label> RuleBuilder<L> {
     pub fn new() -> Self {
-        Self { statement: vec![] }
+        Self {
+            statement: vec![],
+            name: None,
+        }
+    }
+
+    pub fn named(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
     }
 
     pub fn forall(mut self, constraints: impl Into<Constraints<L>>) -> Self {
@@ -21,7 +30,11 @@ impl<L: Label> RuleBuilder<L> {
         self
     }
 
-    pub fn build(self) -> Result<Rule<L>, RuleConstructionError> {
-        Rule::new(self.statement)
+    pub fn build(self) -> Result<Rule<L>, RuleConstructionError<L>> {
+        let rule = Rule::new(self.statement)?;
+        Ok(match self.name {
+            Some(name) => rule.named(name),
+            None => rule,
+        })
     }
 }
@@ -2,17 +2,48 @@ use super::*;
 
 impl<O, M, E> Category<O, M, E> {
     /// Applies the rule constraints to the graph.
+    /// If an error occurs, the changes made so far are undone.
+    #[allow(clippy::type_complexity)]
     pub fn apply_constraints<'a, L: 'a + Label>(
         &mut self,
         constraints: impl IntoIterator<Item = &'a Constraint<L>> + 'a,
         bindings: &Bindings<L>,
-        object_constructor: &impl Fn(Vec<ObjectTag<&Object<O>>>) -> O,
+        object_constructor: &impl Fn(Vec<ObjectTag<&Object<O>, &Morphism<M>>>) -> O,
         morphism_constructor: &impl Fn(
             MorphismConnection<&Object<O>>,
             Vec<MorphismTag<&Object<O>, &Morphism<M>>>,
         ) -> M,
         equality_constructor: impl Fn(&Equality) -> E,
-    ) -> (Vec<Action<O, M, E>>, Bindings<L>) {
+    ) -> Result<(Vec<Action<O, M, E>>, Bindings<L>), ApplyError<L>> {
+        let mut actions = Vec::new();
+        match self.apply_constraints_impl(
+            constraints,
+            bindings,
+            &mut actions,
+            object_constructor,
+            morphism_constructor,
+            equality_constructor,
+        ) {
+            Ok(bindings) => Ok((actions, bindings)),
+            Err(error) => {
+                self.action_undo(actions);
+                Err(error)
+            }
+        }
+    }
+
+    fn apply_constraints_impl<'a, L: 'a + Label>(
+        &mut self,
+        constraints: impl IntoIterator<Item = &'a Constraint<L>> + 'a,
+        bindings: &Bindings<L>,
+        action_history: &mut Vec<Action<O, M, E>>,
+        object_constructor: &impl Fn(Vec<ObjectTag<&Object<O>, &Morphism<M>>>) -> O,
+        morphism_constructor: &impl Fn(
+            MorphismConnection<&Object<O>>,
+            Vec<MorphismTag<&Object<O>, &Morphism<M>>>,
+        ) -> M,
+        equality_constructor: impl Fn(&Equality) -> E,
+    ) -> Result<Bindings<L>, ApplyError<L>> {
         let mut bindings = bindings.clone();
 
         let mut constrained_objects = Vec::new();
@@ -48,15 +79,27 @@ impl<O, M, E> Category<O, M, E> {
         for (label, tags) in constrained_objects {
             let tags = tags
                 .iter()
-                .map(|tag| tag.map_borrowed(|label| bindings.get_object(label).unwrap())) // TODO: proper error handling
-                .collect::<Vec<_>>();
+                .map(|tag| {
+                    require_references(self, &bindings, tag.references())?;
+                    Ok(tag.map_borrowed(
+                        |label| bindings.get_object(label).expect("Checked above"),
+                        |label| bindings.get_morphism(label).expect("Checked above"),
+                    ))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
 
-            if let Some(object) = bindings.get_object(label) {
+            if bindings.get_object(label).is_some() {
+                let object = require_object(self, &bindings, label)?;
                 extend_objects.push((object, tags));
             } else {
                 let label_tags = tags
                     .iter()
-                    .map(|tag| tag.map_borrowed(|id| self.objects.get(id).unwrap())) // TODO: better error handling
+                    .map(|tag| {
+                        tag.map_borrowed(
+                            |id| self.objects.get(id).expect("Object ids are checked"),
+                            |id| self.morphisms.get(id).expect("Morphism ids are checked"),
+                        )
+                    })
                     .collect();
 
                 new_objects.push(Object {
@@ -67,15 +110,14 @@ impl<O, M, E> Category<O, M, E> {
             }
         }
 
-        let mut action_history = Vec::new();
         let mut extend_history = |mut actions: Vec<_>| {
-            actions.extend(std::mem::take(&mut action_history));
-            action_history = actions;
+            actions.extend(std::mem::take(action_history));
+            *action_history = actions;
         };
 
         // Extend vertices
         if extend_objects.len() > 0 {
-            let actions = self.action_do(Action::ExtendObjectTags(extend_objects));
+            let actions = self.action_do_unchecked(Action::ExtendObjectTags(extend_objects));
             if !actions.is_empty() {
                 extend_history(actions);
             }
@@ -97,16 +139,27 @@ impl<O, M, E> Category<O, M, E> {
             let tags = tags
                 .iter()
                 .map(|tag| {
-                    tag.map_borrowed(
-                        |label| bindings.get_object(label).unwrap(), // TODO: proper error handling
-                        |label| bindings.get_morphism(label).unwrap(), // TODO: proper error handling
-                    )
+                    require_references(self, &bindings, tag.references())?;
+                    Ok(tag.map_borrowed(
+                        |label| bindings.get_object(label).expect("Checked above"),
+                        |label| bindings.get_morphism(label).expect("Checked above"),
+                    ))
                 })
-                .collect::<Vec<_>>();
+                .collect::<Result<Vec<_>, _>>()?;
 
-            if let Some(morphism_id) = bindings.get_morphism(label) {
+            if bindings.get_morphism(label).is_some() {
+                let morphism_id = require_morphism(self, &bindings, label)?;
                 extend_morphisms.push((morphism_id, tags));
             } else {
+                let (from, to) = match connection {
+                    MorphismConnection::Regular { from, to } => (from, to),
+                    MorphismConnection::Isomorphism(a, b) => (a, b),
+                };
+                for label in [from, to] {
+                    if bindings.get_object(label).is_some() {
+                        require_object(self, &bindings, label)?;
+                    }
+                }
                 let connection = connection.map_borrowed(|label| {
                     get_object_or_new(
                         label,
@@ -116,14 +169,15 @@ impl<O, M, E> Category<O, M, E> {
                         object_constructor,
                     )
                 });
-                let label_connection = connection.map_borrowed(|id| self.objects.get(id).unwrap()); // TODO: better error handling
+                let label_connection = connection
+                    .map_borrowed(|id| self.objects.get(id).expect("Object ids are checked"));
 
                 let label_tags = tags
                     .iter()
                     .map(|tag| {
                         tag.map_borrowed(
-                            |id| self.objects.get(id).unwrap(), // TODO: better error handling
-                            |id| self.morphisms.get(id).unwrap(), // TODO: better error handling
+                            |id| self.objects.get(id).expect("Object ids are checked"),
+                            |id| self.morphisms.get(id).expect("Morphism ids are checked"),
                         )
                     })
                     .collect();
@@ -142,7 +196,7 @@ impl<O, M, E> Category<O, M, E> {
 
         // Extend edges
         if extend_morphisms.len() > 0 {
-            let actions = self.action_do(Action::ExtendMorphismTags(extend_morphisms));
+            let actions = self.action_do_unchecked(Action::ExtendMorphismTags(extend_morphisms));
             if !actions.is_empty() {
                 extend_history(actions);
             }
@@ -150,7 +204,7 @@ impl<O, M, E> Category<O, M, E> {
 
         // Create new edges
         if new_morphisms.len() > 0 {
-            let actions = self.action_do(Action::NewMorphisms(new_morphisms));
+            let actions = self.action_do_unchecked(Action::NewMorphisms(new_morphisms));
             assert_eq!(actions.len(), 1);
             // Bind new edges
             match &actions[0] {
@@ -193,11 +247,68 @@ impl<O, M, E> Category<O, M, E> {
             .collect::<Vec<_>>();
 
         if constrained_equalities.len() > 0 {
-            let actions = self.action_do(Action::NewEqualities(constrained_equalities));
+            let actions = self.action_do_unchecked(Action::NewEqualities(constrained_equalities));
             extend_history(actions);
         }
 
-        (action_history, bindings)
+        Ok(bindings)
+    }
+}
+
+/// Checks that the referenced labels are bound to existing objects and morphisms.
+fn require_references<O, M, E, L: Label>(
+    category: &Category<O, M, E>,
+    bindings: &Bindings<L>,
+    (objects, morphisms): (Vec<&L>, Vec<&L>),
+) -> Result<(), ApplyError<L>> {
+    for label in objects {
+        require_object(category, bindings, label)?;
+    }
+    for label in morphisms {
+        require_morphism(category, bindings, label)?;
+    }
+    Ok(())
+}
+
+/// Returns the object bound to the label, checking that it exists in the category.
+fn require_object<O, M, E, L: Label>(
+    category: &Category<O, M, E>,
+    bindings: &Bindings<L>,
+    label: &L,
+) -> Result<ObjectId, ApplyError<L>> {
+    let id = bindings
+        .get_object(label)
+        .ok_or_else(|| ApplyError::UnboundObject {
+            label: label.clone(),
+        })?;
+    if category.objects.contains(&id) {
+        Ok(id)
+    } else {
+        Err(ApplyError::UnknownObject {
+            label: label.clone(),
+            id,
+        })
+    }
+}
+
+/// Returns the morphism bound to the label, checking that it exists in the category.
+fn require_morphism<O, M, E, L: Label>(
+    category: &Category<O, M, E>,
+    bindings: &Bindings<L>,
+    label: &L,
+) -> Result<MorphismId, ApplyError<L>> {
+    let id = bindings
+        .get_morphism(label)
+        .ok_or_else(|| ApplyError::UnboundMorphism {
+            label: label.clone(),
+        })?;
+    if category.morphisms.contains(&id) {
+        Ok(id)
+    } else {
+        Err(ApplyError::UnknownMorphism {
+            label: label.clone(),
+            id,
+        })
     }
 }
 
@@ -212,7 +323,7 @@ fn create_vertices<O, M, E, L: Label>(
         .into_iter()
         .map(|object| (None, object))
         .collect();
-    let actions = category.action_do(Action::NewObjects(new_vertices));
+    let actions = category.action_do_unchecked(Action::NewObjects(new_vertices));
     assert_eq!(actions.len(), 1);
     // Bind new vertices
     let new_vertices = match &actions[0] {
@@ -234,7 +345,7 @@ fn get_object_or_new<O, M, E, L: Label>(
     category: &mut Category<O, M, E>,
     bindings: &mut Bindings<L>,
     actions_handler: impl FnMut(Vec<Action<O, M, E>>),
-    object_constructor: impl Fn(Vec<ObjectTag<&Object<O>>>) -> O,
+    object_constructor: impl Fn(Vec<ObjectTag<&Object<O>, &Morphism<M>>>) -> O,
 ) -> ObjectId {
     bindings.get_object(label).unwrap_or_else(|| {
         create_vertices(
@@ -20,13 +20,13 @@ impl<L: Label> ConstraintsBuilder<L> {
     pub fn object<T: Into<L>>(
         mut self,
         label: T,
-        tags: impl IntoIterator<Item = ObjectTag<T>>,
+        tags: impl IntoIterator<Item = ObjectTag<T, T>>,
     ) -> Self {
         self.0.push(Constraint::Object {
             label: label.into(),
             tags: tags
                 .into_iter()
-                .map(|tag| tag.map(|label| label.into()))
+                .map(|tag| tag.map(|label| label.into(), |label| label.into()))
                 .collect(),
         });
         self
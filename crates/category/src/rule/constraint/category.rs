@@ -12,7 +12,12 @@ impl<O, M, E> Category<O, M, E> {
                 tags: object
                     .tags
                     .iter()
-                    .map(|tag| tag.map_borrowed(|&object| get_object_label(object)))
+                    .map(|tag| {
+                        tag.map_borrowed(
+                            |&object| get_object_label(object),
+                            |&morphism| get_morphism_label(morphism),
+                        )
+                    })
                     .collect(),
             })
             .chain(self.morphisms.iter().map(|(&id, morphism)| {
@@ -9,21 +9,43 @@ pub fn constraint_morphism<'a, O, M, E, L: Label>(
 ) -> Box<dyn Iterator<Item = Bindings<L>> + 'a> {
     match bindings.get_morphism(label) {
         Some(morphism) => {
-            let morphism = category
-                .morphisms
-                .get(&morphism)
-                .expect("Invalid bindings: unknown morphism id"); // TODO: return an error
+            // Bindings are checked to be valid in `find_candidates`
+            let morphism = match category.morphisms.get(&morphism) {
+                Some(morphism) => morphism,
+                None => return Box::new(std::iter::empty()),
+            };
             morphism_matches(connection, tags, morphism, bindings)
                 .map_or(Box::new(vec![].into_iter()), |binds| {
                     Box::new(std::iter::once(binds))
                 })
         }
-        None => Box::new(category.morphisms.iter().filter_map(|(&id, morphism)| {
-            morphism_matches(connection, tags, morphism, bindings).map(|mut binds| {
-                binds.bind_morphism(label.clone(), id);
-                binds
-            })
-        })),
+        None => {
+            // Narrow down the search using the endpoint indexes
+            let morphisms = &category.morphisms;
+            let candidates: Box<dyn Iterator<Item = MorphismId>> = match connection {
+                MorphismConnection::Regular { from, to } => {
+                    match (bindings.get_object(from), bindings.get_object(to)) {
+                        (Some(from), Some(to)) => Box::new(morphisms.between(from, to)),
+                        (Some(from), None) => Box::new(morphisms.outgoing(from)),
+                        (None, Some(to)) => Box::new(morphisms.incoming(to)),
+                        (None, None) => Box::new(morphisms.iter().map(|(&id, _)| id)),
+                    }
+                }
+                MorphismConnection::Isomorphism(a, b) => {
+                    match bindings.get_object(a).or_else(|| bindings.get_object(b)) {
+                        Some(object) => Box::new(morphisms.connected(object)),
+                        None => Box::new(morphisms.iter().map(|(&id, _)| id)),
+                    }
+                }
+            };
+            Box::new(candidates.filter_map(move |id| {
+                let morphism = morphisms.get(&id)?;
+                morphism_matches(connection, tags, morphism, bindings).map(|mut binds| {
+                    binds.bind_morphism(label.clone(), id);
+                    binds
+                })
+            }))
+        }
     }
 }
 
@@ -100,6 +122,42 @@ fn tags_matches<L: Label>(
             MorphismTag::ProductP2 => Some(Bindings::new()),
             _ => None,
         }),
+        MorphismTag::CoproductJ1 => tags.iter().find_map(|tag| match tag {
+            MorphismTag::CoproductJ1 => Some(Bindings::new()),
+            _ => None,
+        }),
+        MorphismTag::CoproductJ2 => tags.iter().find_map(|tag| match tag {
+            MorphismTag::CoproductJ2 => Some(Bindings::new()),
+            _ => None,
+        }),
+        MorphismTag::EqualizerInclusion => tags.iter().find_map(|tag| match tag {
+            MorphismTag::EqualizerInclusion => Some(Bindings::new()),
+            _ => None,
+        }),
+        MorphismTag::CoequalizerProjection => tags.iter().find_map(|tag| match tag {
+            MorphismTag::CoequalizerProjection => Some(Bindings::new()),
+            _ => None,
+        }),
+        MorphismTag::PullbackP1 => tags.iter().find_map(|tag| match tag {
+            MorphismTag::PullbackP1 => Some(Bindings::new()),
+            _ => None,
+        }),
+        MorphismTag::PullbackP2 => tags.iter().find_map(|tag| match tag {
+            MorphismTag::PullbackP2 => Some(Bindings::new()),
+            _ => None,
+        }),
+        MorphismTag::PushoutJ1 => tags.iter().find_map(|tag| match tag {
+            MorphismTag::PushoutJ1 => Some(Bindings::new()),
+            _ => None,
+        }),
+        MorphismTag::PushoutJ2 => tags.iter().find_map(|tag| match tag {
+            MorphismTag::PushoutJ2 => Some(Bindings::new()),
+            _ => None,
+        }),
+        MorphismTag::Evaluation => tags.iter().find_map(|tag| match tag {
+            MorphismTag::Evaluation => Some(Bindings::new()),
+            _ => None,
+        }),
         MorphismTag::Identity(constraint) => tags.iter().find_map(|tag| {
             if let &MorphismTag::Identity(object) = tag {
                 bindings.get_object(constraint).map_or_else(
@@ -2,16 +2,17 @@ use super::*;
 
 pub fn constraint_object<'a, O, M, E, L: Label>(
     label: &'a L,
-    tags: &'a [ObjectTag<L>],
+    tags: &'a [ObjectTag<L, L>],
     bindings: &'a Bindings<L>,
     category: &'a Category<O, M, E>,
 ) -> Box<dyn Iterator<Item = Bindings<L>> + 'a> {
     match bindings.get_object(label) {
         Some(object) => {
-            let object = category
-                .objects
-                .get(&object)
-                .expect("Invalid bindings: unknown object id"); // TODO: return an error
+            // Bindings are checked to be valid in `find_candidates`
+            let object = match category.objects.get(&object) {
+                Some(object) => object,
+                None => return Box::new(std::iter::empty()),
+            };
             object_matches(tags, object, bindings).map_or(Box::new(vec![].into_iter()), |binds| {
                 Box::new(std::iter::once(binds))
             })
@@ -26,7 +27,7 @@ pub fn constraint_object<'a, O, M, E, L: Label>(
 }
 
 fn object_matches<O, L: Label>(
-    tags: &[ObjectTag<L>],
+    tags: &[ObjectTag<L, L>],
     object: &Object<O>,
     bindings: &Bindings<L>,
 ) -> Option<Bindings<L>> {
@@ -47,7 +48,7 @@ fn object_matches<O, L: Label>(
 }
 
 fn tag_matches<L: Label>(
-    constraint: &ObjectTag<L>,
+    constraint: &ObjectTag<L, L>,
     tags: &[ObjectTag],
     bindings: &Bindings<L>,
 ) -> Option<Bindings<L>> {
@@ -79,5 +80,83 @@ fn tag_matches<L: Label>(
                 None
             }
         }),
+        ObjectTag::Coproduct(constraint_a, constraint_b) => tags.iter().find_map(|tag| {
+            if let &ObjectTag::Coproduct(object_a, object_b) = tag {
+                constraint_ordered(
+                    vec![constraint_a, constraint_b]
+                        .into_iter()
+                        .map(|label| (label.clone(), bindings.get_object(label))),
+                    vec![object_a, object_b],
+                )
+                .map(|binds| Bindings::from_objects(binds))
+            } else {
+                None
+            }
+        }),
+        ObjectTag::Exponential(constraint_b, constraint_a) => tags.iter().find_map(|tag| {
+            if let &ObjectTag::Exponential(object_b, object_a) = tag {
+                constraint_ordered(
+                    vec![constraint_b, constraint_a]
+                        .into_iter()
+                        .map(|label| (label.clone(), bindings.get_object(label))),
+                    vec![object_b, object_a],
+                )
+                .map(|binds| Bindings::from_objects(binds))
+            } else {
+                None
+            }
+        }),
+        ObjectTag::Equalizer(constraint_f, constraint_g) => tags.iter().find_map(|tag| {
+            if let &ObjectTag::Equalizer(morphism_f, morphism_g) = tag {
+                constraint_ordered(
+                    vec![constraint_f, constraint_g]
+                        .into_iter()
+                        .map(|label| (label.clone(), bindings.get_morphism(label))),
+                    vec![morphism_f, morphism_g],
+                )
+                .map(|binds| Bindings::from_morphisms(binds))
+            } else {
+                None
+            }
+        }),
+        ObjectTag::Coequalizer(constraint_f, constraint_g) => tags.iter().find_map(|tag| {
+            if let &ObjectTag::Coequalizer(morphism_f, morphism_g) = tag {
+                constraint_ordered(
+                    vec![constraint_f, constraint_g]
+                        .into_iter()
+                        .map(|label| (label.clone(), bindings.get_morphism(label))),
+                    vec![morphism_f, morphism_g],
+                )
+                .map(|binds| Bindings::from_morphisms(binds))
+            } else {
+                None
+            }
+        }),
+        ObjectTag::Pullback(constraint_f, constraint_g) => tags.iter().find_map(|tag| {
+            if let &ObjectTag::Pullback(morphism_f, morphism_g) = tag {
+                constraint_ordered(
+                    vec![constraint_f, constraint_g]
+                        .into_iter()
+                        .map(|label| (label.clone(), bindings.get_morphism(label))),
+                    vec![morphism_f, morphism_g],
+                )
+                .map(|binds| Bindings::from_morphisms(binds))
+            } else {
+                None
+            }
+        }),
+        ObjectTag::Pushout(constraint_f, constraint_g) => tags.iter().find_map(|tag| {
+            if let &ObjectTag::Pushout(morphism_f, morphism_g) = tag {
+                constraint_ordered(
+                    vec![constraint_f, constraint_g]
+                        .into_iter()
+                        .map(|label| (label.clone(), bindings.get_morphism(label))),
+                    vec![morphism_f, morphism_g],
+                )
+                .map(|binds| Bindings::from_morphisms(binds))
+            } else {
+                None
+            }
+        }),
     }
 }
@@ -0,0 +1,60 @@
+use super::*;
+
+impl<L: Label> Rule<L> {
+    /// Constructs the dual rule, i.e. the rule that holds in the opposite category.
+    /// Regular morphisms are reversed, compositions are taken in the reverse order,
+    /// and tags are replaced with their duals.
+    /// Returns `None` if a tag has no dual.
+    pub fn dual(&self) -> Option<Self> {
+        let statement = self
+            .statement
+            .iter()
+            .map(|construction| {
+                Some(match construction {
+                    RuleConstruction::Forall(constraints) => {
+                        RuleConstruction::Forall(dual_constraints(constraints)?)
+                    }
+                    RuleConstruction::Exists(constraints) => {
+                        RuleConstruction::Exists(dual_constraints(constraints)?)
+                    }
+                })
+            })
+            .collect::<Option<_>>()?;
+        Some(Self {
+            statement,
+            id: None,
+        })
+    }
+}
+
+fn dual_constraints<L: Label>(constraints: &Constraints<L>) -> Option<Constraints<L>> {
+    constraints
+        .iter()
+        .map(|constraint| {
+            Some(match constraint {
+                Constraint::Object { label, tags } => Constraint::Object {
+                    label: label.clone(),
+                    tags: tags
+                        .iter()
+                        .cloned()
+                        .map(|tag| tag.dual())
+                        .collect::<Option<_>>()?,
+                },
+                Constraint::Morphism {
+                    label,
+                    connection,
+                    tags,
+                } => Constraint::Morphism {
+                    label: label.clone(),
+                    connection: connection.clone().dual(),
+                    tags: tags
+                        .iter()
+                        .cloned()
+                        .map(|tag| tag.dual())
+                        .collect::<Option<_>>()?,
+                },
+                Constraint::Equality(equality) => Constraint::Equality(equality.clone().dual()),
+            })
+        })
+        .collect()
+}
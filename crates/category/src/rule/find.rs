@@ -1,65 +1,197 @@
+use std::rc::Rc;
+
 use super::*;
 
 impl<O, M, E> Category<O, M, E> {
     /// Returns `None` if there are no constraints.
+    /// Fails if the constraints reference a label bound to an id that is not in the category.
+    #[allow(clippy::type_complexity)]
     pub fn find_candidates<'a, L: Label>(
         &'a self,
         constraints: &'a [Constraint<L>],
         bindings: &'a Bindings<L>,
-    ) -> Option<Box<dyn Iterator<Item = Bindings<L>> + 'a>> {
-        let constraint = match constraints.first() {
-            Some(constraint) => constraint,
-            None => return None,
+    ) -> Result<Option<Box<dyn Iterator<Item = Bindings<L>> + 'a>>, ApplyError<L>> {
+        self.check_bindings(constraints, bindings)?;
+        let solver = Rc::new(LazySolver::new(self));
+        Ok(self.find_candidates_impl(constraints.iter().collect(), bindings, solver))
+    }
+
+    /// Check that the labels used in the constraints are bound to existing objects and morphisms.
+    fn check_bindings<L: Label>(
+        &self,
+        constraints: &[Constraint<L>],
+        bindings: &Bindings<L>,
+    ) -> Result<(), ApplyError<L>> {
+        let check_object = |label: &L| match bindings.get_object(label) {
+            Some(id) if !self.objects.contains(&id) => Err(ApplyError::UnknownObject {
+                label: label.clone(),
+                id,
+            }),
+            _ => Ok(()),
         };
-        let constraints = &constraints[1..];
+        let check_morphism = |label: &L| match bindings.get_morphism(label) {
+            Some(id) if !self.morphisms.contains(&id) => Err(ApplyError::UnknownMorphism {
+                label: label.clone(),
+                id,
+            }),
+            _ => Ok(()),
+        };
+
+        for constraint in constraints {
+            match constraint {
+                Constraint::Object { label, tags } => {
+                    check_object(label)?;
+                    for tag in tags {
+                        let (objects, morphisms) = tag.references();
+                        objects.into_iter().try_for_each(check_object)?;
+                        morphisms.into_iter().try_for_each(check_morphism)?;
+                    }
+                }
+                Constraint::Morphism {
+                    label,
+                    connection,
+                    tags,
+                } => {
+                    check_morphism(label)?;
+                    let (from, to) = match connection {
+                        MorphismConnection::Regular { from, to } => (from, to),
+                        MorphismConnection::Isomorphism(a, b) => (a, b),
+                    };
+                    check_object(from)?;
+                    check_object(to)?;
+                    for tag in tags {
+                        let (objects, morphisms) = tag.references();
+                        objects.into_iter().try_for_each(check_object)?;
+                        morphisms.into_iter().try_for_each(check_morphism)?;
+                    }
+                }
+                Constraint::Equality(equality) => {
+                    equality
+                        .left()
+                        .iter()
+                        .chain(equality.right())
+                        .try_for_each(check_morphism)?;
+                }
+            }
+        }
+        Ok(())
+    }
 
-        let binds: Box<dyn Iterator<Item = _>> = match constraint {
-            Constraint::Object { label, tags } => Box::new(process(
+    /// Processes the constraints starting with the cheapest one to check,
+    /// choosing the next constraint each time new labels get bound.
+    fn find_candidates_impl<'a, 's: 'a, L: Label>(
+        &'a self,
+        mut constraints: Vec<&'a Constraint<L>>,
+        bindings: &'a Bindings<L>,
+        solver: Rc<LazySolver<'s, O, M, E>>,
+    ) -> Option<Box<dyn Iterator<Item = Bindings<L>> + 'a>> {
+        let next = constraints
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, constraint)| self.estimate_candidates(constraint, bindings))
+            .map(|(i, _)| i)?;
+        let constraint = constraints.remove(next);
+
+        let binds = match constraint {
+            Constraint::Object { label, tags } => process(
                 constraint_object(label, tags, bindings, self),
                 constraints,
                 bindings,
-                self,
-            )),
+                solver,
+            ),
             Constraint::Morphism {
                 label,
                 connection,
                 tags,
-            } => Box::new(process(
+            } => process(
                 constraint_morphism(label, connection, tags, bindings, self),
                 constraints,
                 bindings,
-                self,
-            )),
-            Constraint::Equality(equality) => Box::new(process(
-                constraint_equality(equality, bindings, self),
+                solver,
+            ),
+            Constraint::Equality(equality) => process(
+                constraint_equality(equality, bindings, solver.clone()),
                 constraints,
                 bindings,
-                self,
-            )),
+                solver,
+            ),
         };
 
         Some(binds)
     }
+
+    /// Estimates the number of candidates the constraint produces given the bindings.
+    /// Equalities with unbound labels are deferred until nothing else is left.
+    fn estimate_candidates<L: Label>(
+        &self,
+        constraint: &Constraint<L>,
+        bindings: &Bindings<L>,
+    ) -> usize {
+        match constraint {
+            Constraint::Object { label, .. } => match bindings.get_object(label) {
+                Some(_) => 1,
+                None => self.objects.len(),
+            },
+            Constraint::Morphism {
+                label, connection, ..
+            } => {
+                if bindings.get_morphism(label).is_some() {
+                    return 1;
+                }
+                let morphisms = &self.morphisms;
+                match connection {
+                    MorphismConnection::Regular { from, to } => {
+                        match (bindings.get_object(from), bindings.get_object(to)) {
+                            (Some(from), Some(to)) => morphisms.between(from, to).count(),
+                            (Some(from), None) => morphisms.outgoing(from).count(),
+                            (None, Some(to)) => morphisms.incoming(to).count(),
+                            (None, None) => morphisms.len(),
+                        }
+                    }
+                    MorphismConnection::Isomorphism(a, b) => {
+                        match bindings.get_object(a).or_else(|| bindings.get_object(b)) {
+                            Some(object) => morphisms.connected(object).count(),
+                            None => morphisms.len(),
+                        }
+                    }
+                }
+            }
+            Constraint::Equality(equality) => {
+                let bound = equality
+                    .left()
+                    .iter()
+                    .chain(equality.right())
+                    .all(|label| bindings.get_morphism(label).is_some());
+                if bound {
+                    1
+                } else {
+                    usize::MAX
+                }
+            }
+        }
+    }
 }
 
-fn process<'a, O, M, E, L: Label>(
+fn process<'a, 's: 'a, O, M, E, L: Label>(
     new_binds: impl Iterator<Item = Bindings<L>> + 'a,
-    constraints: &'a [Constraint<L>],
+    constraints: Vec<&'a Constraint<L>>,
     bindings: &'a Bindings<L>,
-    category: &'a Category<O, M, E>,
-) -> impl Iterator<Item = Bindings<L>> + 'a {
-    new_binds.flat_map(|binds| {
+    solver: Rc<LazySolver<'s, O, M, E>>,
+) -> Box<dyn Iterator<Item = Bindings<L>> + 'a> {
+    Box::new(new_binds.flat_map(move |binds| {
         let mut old_binds = binds.clone();
         old_binds.extend(bindings.clone());
-        let binds = match category.find_candidates(constraints, &old_binds) {
-            Some(new_binds) => new_binds
-                .map(|mut next_binds| {
-                    next_binds.extend(binds.clone());
-                    next_binds
-                })
-                .collect::<Vec<_>>(),
-            None => vec![binds],
-        };
+        let category = solver.category();
+        let binds =
+            match category.find_candidates_impl(constraints.clone(), &old_binds, solver.clone()) {
+                Some(new_binds) => new_binds
+                    .map(|mut next_binds| {
+                        next_binds.extend(binds.clone());
+                        next_binds
+                    })
+                    .collect::<Vec<_>>(),
+                None => vec![binds],
+            };
         binds
-    })
+    }))
 }
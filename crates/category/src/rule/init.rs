@@ -2,13 +2,241 @@ use std::collections::HashMap;
 
 use super::*;
 
-#[derive(Debug, Clone)]
-pub enum RuleConstructionError {}
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleConstructionError<L> {
+    /// The statement has no constructions.
+    EmptyStatement,
+    /// A label is used in a tag or an equality before it is introduced.
+    UndefinedLabel { label: L, construction: usize },
+    /// The same label is used both for an object and for a morphism.
+    ConflictingKinds { label: L, construction: usize },
+    /// A morphism is declared twice with different connections.
+    ConflictingConnections {
+        label: L,
+        construction: usize,
+        first: MorphismConnection<L>,
+        second: MorphismConnection<L>,
+    },
+    /// The paths of an equality do not compose or have different endpoints.
+    NotComposable {
+        equality: Equality<L>,
+        construction: usize,
+    },
+    /// The last construction does not add anything to the category.
+    OutputIntroducesNothing,
+}
 
 impl<L: Label> Rule<L> {
-    pub fn new(statement: RuleStatement<L>) -> Result<Self, RuleConstructionError> {
-        // TODO: check that the statement is valid
-        Ok(Self { statement })
+    pub fn new(statement: RuleStatement<L>) -> Result<Self, RuleConstructionError<L>> {
+        validate(&statement).map_err(|(error, _)| error)?;
+        Ok(Self {
+            statement,
+            id: None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LabelKind {
+    Object,
+    Morphism,
+}
+
+/// Labels introduced so far, with the index of the construction introducing them.
+struct Scope<L> {
+    kinds: HashMap<L, (LabelKind, usize)>,
+    connections: HashMap<L, MorphismConnection<L>>,
+}
+
+impl<L: Label> Scope<L> {
+    /// Introduce a label, checking that it is not already used for the other kind.
+    fn introduce(
+        &mut self,
+        label: &L,
+        kind: LabelKind,
+        construction: usize,
+    ) -> Result<bool, RuleConstructionError<L>> {
+        match self.kinds.get(label) {
+            Some(&(existing, _)) if existing == kind => Ok(false),
+            Some(_) => Err(RuleConstructionError::ConflictingKinds {
+                label: label.clone(),
+                construction,
+            }),
+            None => {
+                self.kinds.insert(label.clone(), (kind, construction));
+                Ok(true)
+            }
+        }
+    }
+
+    /// Check that the label has been introduced with the given kind.
+    /// With `strict`, the label must come from an earlier construction.
+    fn require(
+        &self,
+        label: &L,
+        kind: LabelKind,
+        construction: usize,
+        strict: bool,
+    ) -> Result<(), RuleConstructionError<L>> {
+        match self.kinds.get(label) {
+            Some(&(existing, introduced)) if existing == kind => {
+                if strict && introduced >= construction {
+                    Err(RuleConstructionError::UndefinedLabel {
+                        label: label.clone(),
+                        construction,
+                    })
+                } else {
+                    Ok(())
+                }
+            }
+            Some(_) => Err(RuleConstructionError::ConflictingKinds {
+                label: label.clone(),
+                construction,
+            }),
+            None => Err(RuleConstructionError::UndefinedLabel {
+                label: label.clone(),
+                construction,
+            }),
+        }
+    }
+
+    /// Returns the source and the target of a path,
+    /// or `None` if the path does not compose.
+    fn path_endpoints<'a>(&'a self, path: &[L]) -> Option<(&'a L, &'a L)> {
+        let mut endpoints: Option<(&L, &L)> = None;
+        for label in path {
+            let (from, to) = match self.connections.get(label)? {
+                MorphismConnection::Regular { from, to } => (from, to),
+                MorphismConnection::Isomorphism(..) => return None,
+            };
+            endpoints = match endpoints {
+                None => Some((from, to)),
+                Some((source, last)) if last == from => Some((source, to)),
+                Some(_) => return None,
+            };
+        }
+        endpoints
+    }
+}
+
+/// The construction and the constraint in it where a statement is invalid,
+/// or `None` if the statement is invalid as a whole.
+pub(crate) type ErrorLocation = Option<(usize, usize)>;
+
+/// Check that the statement can be applied without referencing unknown labels.
+///
+/// In `exists` constructions, tags are resolved before the new objects and morphisms
+/// are created, so object tags and morphism arguments of tags
+/// must refer to labels from earlier constructions.
+pub(crate) fn validate<L: Label>(
+    statement: &RuleStatement<L>,
+) -> Result<(), (RuleConstructionError<L>, ErrorLocation)> {
+    if statement.is_empty() {
+        return Err((RuleConstructionError::EmptyStatement, None));
+    }
+
+    let mut scope = Scope {
+        kinds: HashMap::new(),
+        connections: HashMap::new(),
+    };
+    let mut introduces_anything = false;
+
+    for (index, construction) in statement.iter().enumerate() {
+        let (constraints, exists) = match construction {
+            RuleConstruction::Forall(constraints) => (constraints, false),
+            RuleConstruction::Exists(constraints) => (constraints, true),
+        };
+        introduces_anything = false;
+
+        for (position, constraint) in constraints.iter().enumerate() {
+            introduces_anything |= validate_constraint(&mut scope, constraint, index, exists)
+                .map_err(|error| (error, Some((index, position))))?;
+        }
+
+        if !exists {
+            introduces_anything = false;
+        }
+    }
+
+    if !introduces_anything {
+        return Err((RuleConstructionError::OutputIntroducesNothing, None));
+    }
+
+    Ok(())
+}
+
+/// Checks the constraint and introduces its labels into the scope.
+/// Returns whether the constraint adds anything.
+fn validate_constraint<L: Label>(
+    scope: &mut Scope<L>,
+    constraint: &Constraint<L>,
+    index: usize,
+    exists: bool,
+) -> Result<bool, RuleConstructionError<L>> {
+    match constraint {
+        Constraint::Object { label, tags } => {
+            for tag in tags {
+                let (objects, morphisms) = tag.references();
+                for object in objects {
+                    scope.require(object, LabelKind::Object, index, exists)?;
+                }
+                for morphism in morphisms {
+                    scope.require(morphism, LabelKind::Morphism, index, exists)?;
+                }
+            }
+            let new = scope.introduce(label, LabelKind::Object, index)?;
+            Ok(new || !tags.is_empty())
+        }
+        Constraint::Morphism {
+            label,
+            connection,
+            tags,
+        } => {
+            for tag in tags {
+                let (objects, morphisms) = tag.references();
+                for object in objects {
+                    scope.require(object, LabelKind::Object, index, false)?;
+                }
+                for morphism in morphisms {
+                    scope.require(morphism, LabelKind::Morphism, index, exists)?;
+                }
+            }
+            let (a, b) = match connection {
+                MorphismConnection::Isomorphism(a, b)
+                | MorphismConnection::Regular { from: a, to: b } => (a, b),
+            };
+            let mut new = scope.introduce(a, LabelKind::Object, index)?;
+            new |= scope.introduce(b, LabelKind::Object, index)?;
+
+            if scope.introduce(label, LabelKind::Morphism, index)? {
+                scope.connections.insert(label.clone(), connection.clone());
+                new = true;
+            } else if let Some(first) = scope.connections.get(label) {
+                if first != connection {
+                    return Err(RuleConstructionError::ConflictingConnections {
+                        label: label.clone(),
+                        construction: index,
+                        first: first.clone(),
+                        second: connection.clone(),
+                    });
+                }
+            }
+            Ok(new || !tags.is_empty())
+        }
+        Constraint::Equality(equality) => {
+            for label in equality.left().iter().chain(equality.right()) {
+                scope.require(label, LabelKind::Morphism, index, false)?;
+            }
+            let left = scope.path_endpoints(equality.left());
+            let right = scope.path_endpoints(equality.right());
+            if left.is_none() || left != right {
+                return Err(RuleConstructionError::NotComposable {
+                    equality: equality.clone(),
+                    construction: index,
+                });
+            }
+            Ok(true)
+        }
     }
 }
 
@@ -40,7 +268,7 @@ pub enum RuleInput<L> {
 impl<O, M, E> Category<O, M, E> {
     pub fn from_rule<L: Label>(
         rule: &Rule<L>,
-        object_constructor: impl Fn(RulePart, &L, &Vec<ObjectTag<L>>) -> O,
+        object_constructor: impl Fn(RulePart, &L, &Vec<ObjectTag<L, L>>) -> O,
         morphism_constructor: impl Fn(RulePart, &L, &Vec<MorphismTag<L, L>>) -> M,
         equality_constructor: impl Fn(RulePart, &Equality<L>) -> E,
     ) -> (Self, Vec<RuleInput<L>>, Bindings<L>) {
@@ -120,7 +348,7 @@ fn add_constraints<'a, O, M, E, L: 'a + Label>(
     statement: &[RuleConstruction<L>],
     bindings: &mut Bindings<L>,
     category: &mut Category<O, M, E>,
-    object_constructor: impl Fn(RulePart, &L, &Vec<ObjectTag<L>>) -> O,
+    object_constructor: impl Fn(RulePart, &L, &Vec<ObjectTag<L, L>>) -> O,
     morphism_constructor: impl Fn(RulePart, &L, &Vec<MorphismTag<L, L>>) -> M,
     equality_constructor: impl Fn(RulePart, &Equality<L>) -> E,
 ) -> Vec<RuleInput<L>> {
@@ -130,9 +358,10 @@ fn add_constraints<'a, O, M, E, L: 'a + Label>(
         label: &L,
         rule_part: RulePart,
         objects: &mut HashMap<L, ObjectId>,
+        morphisms: &HashMap<L, MorphismId>,
         category: &mut Category<O, M, E>,
         statement: &[RuleConstruction<L>],
-        object_constructor: &impl Fn(RulePart, &L, &Vec<ObjectTag<L>>) -> O,
+        object_constructor: &impl Fn(RulePart, &L, &Vec<ObjectTag<L, L>>) -> O,
     ) -> ObjectId {
         objects.get(label).copied().unwrap_or_else(|| {
             let empty_tags = vec![];
@@ -151,16 +380,24 @@ fn add_constraints<'a, O, M, E, L: 'a + Label>(
             let tags = tags
                 .iter()
                 .map(|tag| {
-                    tag.map_borrowed(|label| {
-                        get_object(
-                            label,
-                            rule_part,
-                            objects,
-                            category,
-                            statement,
-                            object_constructor,
-                        )
-                    })
+                    tag.map_borrowed(
+                        |label| {
+                            get_object(
+                                label,
+                                rule_part,
+                                objects,
+                                morphisms,
+                                category,
+                                statement,
+                                object_constructor,
+                            )
+                        },
+                        |label| {
+                            *morphisms
+                                .get(label)
+                                .expect("Object tags expect morphisms to be created beforehand")
+                        },
+                    )
                 })
                 .collect();
 
@@ -192,6 +429,7 @@ fn add_constraints<'a, O, M, E, L: 'a + Label>(
                     label,
                     RulePart::Inferred,
                     &mut bindings.objects,
+                    &bindings.morphisms,
                     category,
                     statement,
                     object_constructor,
@@ -208,6 +446,7 @@ fn add_constraints<'a, O, M, E, L: 'a + Label>(
                                 label,
                                 RulePart::Inferred,
                                 &mut bindings.objects,
+                                &bindings.morphisms,
                                 category,
                                 statement,
                                 object_constructor,
@@ -238,6 +477,7 @@ fn add_constraints<'a, O, M, E, L: 'a + Label>(
                     label,
                     rule_part,
                     &mut bindings.objects,
+                    &bindings.morphisms,
                     category,
                     statement,
                     object_constructor,
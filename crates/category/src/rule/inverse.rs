@@ -1,10 +1,23 @@
 use super::*;
 
 impl<L: Label> Rule<L> {
+    /// The inverse rules are identified by the id of the rule and their index.
     pub fn invert(&self) -> Vec<Self> {
+        let name = self
+            .id
+            .as_ref()
+            .filter(|id| id.inverse.is_none())
+            .map(|id| &id.name);
         invert_statement(&self.statement)
             .into_iter()
-            .map(|statement| Self { statement })
+            .enumerate()
+            .map(|(index, statement)| Self {
+                statement,
+                id: name.map(|name| RuleId {
+                    name: name.clone(),
+                    inverse: Some(index),
+                }),
+            })
             .collect()
     }
 }
@@ -2,24 +2,36 @@ mod apply;
 pub mod axioms;
 mod builder;
 pub mod constraint;
+mod dual;
 pub mod find;
 mod init;
 mod inverse;
+pub mod text;
 
 use super::*;
 
+pub use apply::*;
 pub use builder::*;
 use constraint::*;
 pub use init::*;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Rule<L: Label> {
     statement: RuleStatement<L>,
+    id: Option<RuleId>,
+}
+
+/// Identifies a rule independently of its position in a list of rules.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct RuleId {
+    pub name: String,
+    /// The index of the inverse rule, if the rule was inverted.
+    pub inverse: Option<usize>,
 }
 
 pub type RuleStatement<L> = Vec<RuleConstruction<L>>;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum RuleConstruction<L: Label> {
     Forall(Constraints<L>),
     Exists(Constraints<L>),
@@ -27,10 +39,13 @@ pub enum RuleConstruction<L: Label> {
 
 pub type Constraints<L> = Vec<Constraint<L>>;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Constraint<L: Label> {
     /// Require an object to exist
-    Object { label: L, tags: Vec<ObjectTag<L>> },
+    Object {
+        label: L,
+        tags: Vec<ObjectTag<L, L>>,
+    },
     /// Require a morphism to exist
     Morphism {
         label: L,
@@ -42,7 +57,47 @@ pub enum Constraint<L: Label> {
     Equality(Equality<L>),
 }
 
+impl<'de, L: Label + Deserialize<'de>> Deserialize<'de> for Rule<L> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct RuleRepr<L: Label> {
+            statement: RuleStatement<L>,
+            #[serde(default)]
+            id: Option<RuleId>,
+        }
+
+        let repr = RuleRepr::deserialize(deserializer)?;
+        let rule = Self::new(repr.statement)
+            .map_err(|error| serde::de::Error::custom(format!("invalid rule: {error:?}")))?;
+        Ok(Self {
+            id: repr.id,
+            ..rule
+        })
+    }
+}
+
+impl RuleId {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            inverse: None,
+        }
+    }
+}
+
 impl<L: Label> Rule<L> {
+    /// Names the rule, so that derivations and proof scripts can refer to it.
+    pub fn named(self, name: impl Into<String>) -> Self {
+        Self {
+            id: Some(RuleId::new(name)),
+            ..self
+        }
+    }
+
+    pub fn id(&self) -> Option<&RuleId> {
+        self.id.as_ref()
+    }
+
     pub fn get_statement(&self) -> &RuleStatement<L> {
         &self.statement
     }
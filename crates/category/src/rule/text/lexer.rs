@@ -0,0 +1,155 @@
+use std::ops::Range;
+
+use super::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Token<'a> {
+    Forall,
+    Exists,
+    Label(&'a str),
+    Colon,
+    Comma,
+    Dot,
+    LeftBracket,
+    RightBracket,
+    LeftParen,
+    RightParen,
+    Arrow,
+    IsoArrow,
+    Equals,
+    Compose,
+}
+
+impl Token<'_> {
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Forall => "`forall`".to_owned(),
+            Self::Exists => "`exists`".to_owned(),
+            Self::Label(label) => format!("label `{label}`"),
+            Self::Colon => "`:`".to_owned(),
+            Self::Comma => "`,`".to_owned(),
+            Self::Dot => "`.`".to_owned(),
+            Self::LeftBracket => "`[`".to_owned(),
+            Self::RightBracket => "`]`".to_owned(),
+            Self::LeftParen => "`(`".to_owned(),
+            Self::RightParen => "`)`".to_owned(),
+            Self::Arrow => "`->`".to_owned(),
+            Self::IsoArrow => "`<->`".to_owned(),
+            Self::Equals => "`=`".to_owned(),
+            Self::Compose => "`∘`".to_owned(),
+        }
+    }
+}
+
+pub(super) const KEYWORDS: [&str; 2] = ["forall", "exists"];
+
+/// Checks whether the character can be a part of an unquoted label.
+pub(super) fn is_label_char(c: char) -> bool {
+    !c.is_whitespace() && !":,.[]()=∘\"-<>".contains(c)
+}
+
+/// Returns the number of `#` opening a quoted label that may contain quotes,
+/// or `None` if the text does not start with such a label.
+fn raw_quotes(text: &str) -> Option<usize> {
+    let hashes = text.chars().take_while(|&c| c == '#').count();
+    (hashes > 0 && text[hashes..].starts_with('"')).then_some(hashes)
+}
+
+pub(super) fn tokenize(text: &str) -> Result<Vec<(Token<'_>, Range<usize>)>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        let single = |token| Some((token, start..start + c.len_utf8()));
+        let token = match c {
+            c if c.is_whitespace() => None,
+            ':' => single(Token::Colon),
+            ',' => single(Token::Comma),
+            '.' => single(Token::Dot),
+            '[' => single(Token::LeftBracket),
+            ']' => single(Token::RightBracket),
+            '(' => single(Token::LeftParen),
+            ')' => single(Token::RightParen),
+            '=' => single(Token::Equals),
+            '∘' => single(Token::Compose),
+            '-' => match chars.next() {
+                Some((_, '>')) => Some((Token::Arrow, start..start + 2)),
+                _ => {
+                    return Err(ParseError {
+                        span: start..start + 1,
+                        message: "expected `->`".to_owned(),
+                    })
+                }
+            },
+            '<' => match (chars.next(), chars.next()) {
+                (Some((_, '-')), Some((_, '>'))) => Some((Token::IsoArrow, start..start + 3)),
+                _ => {
+                    return Err(ParseError {
+                        span: start..start + 1,
+                        message: "expected `<->`".to_owned(),
+                    })
+                }
+            },
+            '"' => {
+                let label_start = start + 1;
+                let label_end = loop {
+                    match chars.next() {
+                        Some((end, '"')) => break end,
+                        Some(_) => (),
+                        None => {
+                            return Err(ParseError {
+                                span: start..text.len(),
+                                message: "unterminated quoted label".to_owned(),
+                            })
+                        }
+                    }
+                };
+                Some((
+                    Token::Label(&text[label_start..label_end]),
+                    start..label_end + 1,
+                ))
+            }
+            '#' if raw_quotes(&text[start..]).is_some() => {
+                let hashes = raw_quotes(&text[start..]).unwrap_or_default();
+                let label_start = start + hashes + 1;
+                let closing = format!("\"{}", "#".repeat(hashes));
+                let Some(label_len) = text[label_start..].find(&closing) else {
+                    return Err(ParseError {
+                        span: start..text.len(),
+                        message: "unterminated quoted label".to_owned(),
+                    });
+                };
+                let label_end = label_start + label_len;
+                let end = label_end + closing.len();
+                while chars.next_if(|&(i, _)| i < end).is_some() {}
+                Some((Token::Label(&text[label_start..label_end]), start..end))
+            }
+            c if is_label_char(c) => {
+                let mut end = start + c.len_utf8();
+                while let Some(&(i, c)) = chars.peek() {
+                    if !is_label_char(c) {
+                        break;
+                    }
+                    end = i + c.len_utf8();
+                    chars.next();
+                }
+                let word = &text[start..end];
+                let token = match word {
+                    "forall" => Token::Forall,
+                    "exists" => Token::Exists,
+                    _ => Token::Label(word),
+                };
+                Some((token, start..end))
+            }
+            c => {
+                return Err(ParseError {
+                    span: start..start + c.len_utf8(),
+                    message: format!("unexpected character `{c}`"),
+                })
+            }
+        };
+        tokens.extend(token);
+    }
+
+    Ok(tokens)
+}
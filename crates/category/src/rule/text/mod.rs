@@ -0,0 +1,130 @@
+//! A textual language for rules.
+//!
+//! A rule is a sequence of constructions separated by dots.
+//! Each construction starts with `forall` or `exists`
+//! and is followed by comma separated constraints:
+//! ```text
+//! forall f: A -> B, g: B -> C.
+//! exists g∘f: A -> C [composition(f, g)].
+//! ```
+//!
+//! Constraints can be:
+//! - objects: `A` or `AxB [product(A, B)]`
+//! - morphisms: `f: A -> B` or `f: A -> B [unique]`
+//! - isomorphisms: `f: A <-> B`
+//! - equalities of compositions, written right to left: `p1 ∘ m = f`
+//!
+//! Labels consisting of letters, digits, and symbols other than
+//! `:,.[]()=∘"-<>` can be written as is, other labels have to be quoted: `"g o f"`.
+//! Labels containing quotes are quoted with enough `#` around the quotes
+//! to not appear inside the label: `#"say "hi""#`.
+//! A morphism can also be labelled with the composition it stands for:
+//! `exists g∘f: A -> C [composition(f, g)]`.
+//! The label is the composition as written, and can be referred to later as `"g∘f"`.
+
+mod lexer;
+mod parser;
+mod printer;
+
+use super::*;
+
+use lexer::*;
+pub use parser::*;
+pub use printer::*;
+
+impl<L: Label> Rule<L> {
+    /// Parses the rule from its text form.
+    pub fn parse<'a>(text: &'a str) -> Result<Self, ParseError>
+    where
+        L: From<&'a str>,
+    {
+        let (statement, spans) = parse_statement_spanned(text)?;
+        validate(&statement).map_err(|(error, location)| {
+            // Point at the offending constraint, or at the output for the whole rule
+            let span = match location {
+                Some((construction, constraint)) => spans[construction].1[constraint].clone(),
+                None => spans.last().map_or(0..text.len(), |(span, _)| span.clone()),
+            };
+            ParseError {
+                span,
+                message: format!("invalid rule: {error:?}"),
+            }
+        })?;
+        Ok(Self {
+            statement,
+            id: None,
+        })
+    }
+
+    /// Prints the rule in its text form, which can be parsed back with [`Rule::parse`].
+    pub fn to_text(&self) -> String
+    where
+        L: AsRef<str>,
+    {
+        print_statement(self.get_statement())
+    }
+}
+
+const OBJECT_TAGS: [(&str, usize); 9] = [
+    ("initial", 0),
+    ("terminal", 0),
+    ("product", 2),
+    ("coproduct", 2),
+    ("exponential", 2),
+    ("equalizer", 2),
+    ("coequalizer", 2),
+    ("pullback", 2),
+    ("pushout", 2),
+];
+
+const MORPHISM_TAGS: [(&str, usize); 15] = [
+    ("identity", 1),
+    ("unique", 0),
+    ("composition", 2),
+    ("isomorphism", 2),
+    ("product_p1", 0),
+    ("product_p2", 0),
+    ("coproduct_j1", 0),
+    ("coproduct_j2", 0),
+    ("equalizer_inclusion", 0),
+    ("coequalizer_projection", 0),
+    ("pullback_p1", 0),
+    ("pullback_p2", 0),
+    ("pushout_j1", 0),
+    ("pushout_j2", 0),
+    ("evaluation", 0),
+];
+
+fn object_tag_name<O, M>(tag: &ObjectTag<O, M>) -> &'static str {
+    match tag {
+        ObjectTag::Initial => "initial",
+        ObjectTag::Terminal => "terminal",
+        ObjectTag::Product(_, _) => "product",
+        ObjectTag::Coproduct(_, _) => "coproduct",
+        ObjectTag::Exponential(_, _) => "exponential",
+        ObjectTag::Equalizer(_, _) => "equalizer",
+        ObjectTag::Coequalizer(_, _) => "coequalizer",
+        ObjectTag::Pullback(_, _) => "pullback",
+        ObjectTag::Pushout(_, _) => "pushout",
+    }
+}
+
+fn morphism_tag_name<O, M>(tag: &MorphismTag<O, M>) -> &'static str {
+    match tag {
+        MorphismTag::Identity(_) => "identity",
+        MorphismTag::Unique => "unique",
+        MorphismTag::Composition { .. } => "composition",
+        MorphismTag::Isomorphism(_, _) => "isomorphism",
+        MorphismTag::ProductP1 => "product_p1",
+        MorphismTag::ProductP2 => "product_p2",
+        MorphismTag::CoproductJ1 => "coproduct_j1",
+        MorphismTag::CoproductJ2 => "coproduct_j2",
+        MorphismTag::EqualizerInclusion => "equalizer_inclusion",
+        MorphismTag::CoequalizerProjection => "coequalizer_projection",
+        MorphismTag::PullbackP1 => "pullback_p1",
+        MorphismTag::PullbackP2 => "pullback_p2",
+        MorphismTag::PushoutJ1 => "pushout_j1",
+        MorphismTag::PushoutJ2 => "pushout_j2",
+        MorphismTag::Evaluation => "evaluation",
+    }
+}
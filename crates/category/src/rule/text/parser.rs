@@ -0,0 +1,340 @@
+use std::ops::Range;
+
+use super::*;
+
+/// An error produced when parsing a rule from text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The byte range of the text that caused the error.
+    pub span: Range<usize>,
+    pub message: String,
+}
+
+impl ParseError {
+    /// Formats the error with the line and column of the span,
+    /// followed by the offending line with the span underlined.
+    pub fn report(&self, text: &str) -> String {
+        let start = self.span.start.min(text.len());
+        let line_start = text[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = text[start..].find('\n').map_or(text.len(), |i| start + i);
+        let line = &text[line_start..line_end];
+        let line_number = text[..start].matches('\n').count() + 1;
+        let column = text[line_start..start].chars().count();
+        let width = text[start..self.span.end.clamp(start, line_end)]
+            .chars()
+            .count()
+            .max(1);
+        format!(
+            "error at {}:{}: {}\n{}\n{}{}",
+            line_number,
+            column + 1,
+            self.message,
+            line,
+            " ".repeat(column),
+            "^".repeat(width)
+        )
+    }
+}
+
+/// Parses a rule statement from the text form.
+pub fn parse_statement<'a, L: Label + From<&'a str>>(
+    text: &'a str,
+) -> Result<RuleStatement<L>, ParseError> {
+    parse_statement_spanned(text).map(|(statement, _)| statement)
+}
+
+/// The spans of the constructions of a statement, and of the constraints in them.
+pub(super) type StatementSpans = Vec<(Range<usize>, Vec<Range<usize>>)>;
+
+/// Parses a rule statement, together with the spans of its parts.
+pub(super) fn parse_statement_spanned<'a, L: Label + From<&'a str>>(
+    text: &'a str,
+) -> Result<(RuleStatement<L>, StatementSpans), ParseError> {
+    let tokens = tokenize(text)?;
+    let mut parser = Parser {
+        text,
+        tokens,
+        position: 0,
+    };
+    parser.statement()
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    tokens: Vec<(Token<'a>, Range<usize>)>,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<Token<'a>> {
+        self.tokens.get(self.position).map(|(token, _)| *token)
+    }
+
+    fn span(&self) -> Range<usize> {
+        let end = self.text.len();
+        self.tokens
+            .get(self.position)
+            .map_or(end..end, |(_, span)| span.clone())
+    }
+
+    /// The end of the last consumed token.
+    fn previous_end(&self) -> usize {
+        self.position
+            .checked_sub(1)
+            .and_then(|previous| self.tokens.get(previous))
+            .map_or(0, |(_, span)| span.end)
+    }
+
+    fn error(&self, expected: &str) -> ParseError {
+        let found = self
+            .peek()
+            .map_or_else(|| "end of input".to_owned(), |token| token.describe());
+        ParseError {
+            span: self.span(),
+            message: format!("expected {expected}, found {found}"),
+        }
+    }
+
+    fn next_if(&mut self, token: Token) -> bool {
+        if self.peek() == Some(token) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: Token, expected: &str) -> Result<(), ParseError> {
+        if self.next_if(token) {
+            Ok(())
+        } else {
+            Err(self.error(expected))
+        }
+    }
+
+    fn label<L: From<&'a str>>(&mut self) -> Result<L, ParseError> {
+        match self.peek() {
+            Some(Token::Label(label)) => {
+                self.position += 1;
+                Ok(label.into())
+            }
+            _ => Err(self.error("a label")),
+        }
+    }
+
+    fn statement<L: Label + From<&'a str>>(
+        &mut self,
+    ) -> Result<(RuleStatement<L>, StatementSpans), ParseError> {
+        let mut statement = Vec::new();
+        let mut spans = Vec::new();
+        while self.peek().is_some() {
+            let (construction, construction_spans) = self.construction()?;
+            statement.push(construction);
+            spans.push(construction_spans);
+            if !self.next_if(Token::Dot) && self.peek().is_some() {
+                return Err(self.error("`.` or `,`"));
+            }
+        }
+        Ok((statement, spans))
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn construction<L: Label + From<&'a str>>(
+        &mut self,
+    ) -> Result<(RuleConstruction<L>, (Range<usize>, Vec<Range<usize>>)), ParseError> {
+        let start = self.span().start;
+        let forall = match self.peek() {
+            Some(Token::Forall) => true,
+            Some(Token::Exists) => false,
+            _ => return Err(self.error("`forall` or `exists`")),
+        };
+        self.position += 1;
+
+        let mut constraints = Vec::new();
+        let mut spans = Vec::new();
+        if !matches!(self.peek(), None | Some(Token::Dot)) {
+            loop {
+                let constraint_start = self.span().start;
+                constraints.push(self.constraint()?);
+                spans.push(constraint_start..self.previous_end());
+                if !self.next_if(Token::Comma) {
+                    break;
+                }
+            }
+        }
+
+        let construction = if forall {
+            RuleConstruction::Forall(constraints)
+        } else {
+            RuleConstruction::Exists(constraints)
+        };
+        Ok((construction, (start..self.previous_end(), spans)))
+    }
+
+    fn constraint<L: Label + From<&'a str>>(&mut self) -> Result<Constraint<L>, ParseError> {
+        let start = self.span().start;
+        let path = self.path()?;
+
+        if self.next_if(Token::Equals) {
+            let right = self.path()?;
+            return Ok(Constraint::Equality(
+                Equality::new(path, right).expect("Paths are never empty"),
+            ));
+        }
+
+        let label = if path.len() == 1 {
+            path.into_iter().next().unwrap()
+        } else if self.peek() == Some(Token::Colon) {
+            // A morphism labelled with the composition it stands for
+            self.text[start..self.previous_end()].into()
+        } else {
+            return Err(self.error("`=` or `:`"));
+        };
+
+        if !self.next_if(Token::Colon) {
+            let tags = self.tags(&OBJECT_TAGS)?;
+            let tags = tags
+                .into_iter()
+                .map(|(name, args)| object_tag(name, args))
+                .collect();
+            return Ok(Constraint::Object { label, tags });
+        }
+
+        let from = self.label()?;
+        let iso = match self.peek() {
+            Some(Token::Arrow) => false,
+            Some(Token::IsoArrow) => true,
+            _ => return Err(self.error("`->` or `<->`")),
+        };
+        self.position += 1;
+        let to = self.label()?;
+        let connection = if iso {
+            MorphismConnection::Isomorphism(from, to)
+        } else {
+            MorphismConnection::Regular { from, to }
+        };
+        let tags = self.tags(&MORPHISM_TAGS)?;
+        let tags = tags
+            .into_iter()
+            .map(|(name, args)| morphism_tag(name, args))
+            .collect();
+        Ok(Constraint::Morphism {
+            label,
+            connection,
+            tags,
+        })
+    }
+
+    /// Parses a composition written right to left, and returns it in the order of application.
+    fn path<L: From<&'a str>>(&mut self) -> Result<Vec<L>, ParseError> {
+        let mut path = vec![self.label()?];
+        while self.next_if(Token::Compose) {
+            path.push(self.label()?);
+        }
+        path.reverse();
+        Ok(path)
+    }
+
+    fn tags<L: From<&'a str>>(
+        &mut self,
+        known: &[(&'static str, usize)],
+    ) -> Result<Vec<(&'static str, Vec<L>)>, ParseError> {
+        let mut tags = Vec::new();
+        if !self.next_if(Token::LeftBracket) {
+            return Ok(tags);
+        }
+
+        loop {
+            let span = self.span();
+            let name = match self.peek() {
+                Some(Token::Label(name)) => name,
+                _ => return Err(self.error("a tag")),
+            };
+            let &(name, arity) =
+                known
+                    .iter()
+                    .find(|(known, _)| *known == name)
+                    .ok_or_else(|| ParseError {
+                        span: span.clone(),
+                        message: format!(
+                            "unknown tag `{name}`, expected one of: {}",
+                            known
+                                .iter()
+                                .map(|(name, _)| *name)
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        ),
+                    })?;
+            self.position += 1;
+
+            let mut args = Vec::new();
+            if self.next_if(Token::LeftParen) {
+                args.push(self.label()?);
+                while self.next_if(Token::Comma) {
+                    args.push(self.label()?);
+                }
+                self.expect(Token::RightParen, "`)` or `,`")?;
+            }
+            if args.len() != arity {
+                return Err(ParseError {
+                    span: span.start..self.span().start,
+                    message: format!(
+                        "tag `{name}` expects {arity} argument(s), found {}",
+                        args.len()
+                    ),
+                });
+            }
+            tags.push((name, args));
+
+            if !self.next_if(Token::Comma) {
+                break;
+            }
+        }
+        self.expect(Token::RightBracket, "`]` or `,`")?;
+
+        Ok(tags)
+    }
+}
+
+fn object_tag<L>(name: &str, args: Vec<L>) -> ObjectTag<L, L> {
+    let mut args = args.into_iter();
+    let mut arg = || args.next().expect("Tag arity is checked when parsing");
+    match name {
+        "initial" => ObjectTag::Initial,
+        "terminal" => ObjectTag::Terminal,
+        "product" => ObjectTag::Product(arg(), arg()),
+        "coproduct" => ObjectTag::Coproduct(arg(), arg()),
+        "exponential" => ObjectTag::Exponential(arg(), arg()),
+        "equalizer" => ObjectTag::Equalizer(arg(), arg()),
+        "coequalizer" => ObjectTag::Coequalizer(arg(), arg()),
+        "pullback" => ObjectTag::Pullback(arg(), arg()),
+        "pushout" => ObjectTag::Pushout(arg(), arg()),
+        _ => unreachable!("Tag names are checked when parsing"),
+    }
+}
+
+fn morphism_tag<L>(name: &str, args: Vec<L>) -> MorphismTag<L, L> {
+    let mut args = args.into_iter();
+    let mut arg = || args.next().expect("Tag arity is checked when parsing");
+    match name {
+        "identity" => MorphismTag::Identity(arg()),
+        "unique" => MorphismTag::Unique,
+        "composition" => MorphismTag::Composition {
+            first: arg(),
+            second: arg(),
+        },
+        "isomorphism" => MorphismTag::Isomorphism(arg(), arg()),
+        "product_p1" => MorphismTag::ProductP1,
+        "product_p2" => MorphismTag::ProductP2,
+        "coproduct_j1" => MorphismTag::CoproductJ1,
+        "coproduct_j2" => MorphismTag::CoproductJ2,
+        "equalizer_inclusion" => MorphismTag::EqualizerInclusion,
+        "coequalizer_projection" => MorphismTag::CoequalizerProjection,
+        "pullback_p1" => MorphismTag::PullbackP1,
+        "pullback_p2" => MorphismTag::PullbackP2,
+        "pushout_j1" => MorphismTag::PushoutJ1,
+        "pushout_j2" => MorphismTag::PushoutJ2,
+        "evaluation" => MorphismTag::Evaluation,
+        _ => unreachable!("Tag names are checked when parsing"),
+    }
+}
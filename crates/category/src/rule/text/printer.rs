@@ -0,0 +1,150 @@
+use super::*;
+
+/// Prints the rule statement in the text form,
+/// which can be parsed back with [`parse_statement`].
+pub fn print_statement<L: Label + AsRef<str>>(statement: &RuleStatement<L>) -> String {
+    statement
+        .iter()
+        .map(|construction| {
+            let (keyword, constraints) = match construction {
+                RuleConstruction::Forall(constraints) => ("forall", constraints),
+                RuleConstruction::Exists(constraints) => ("exists", constraints),
+            };
+            let constraints = constraints
+                .iter()
+                .map(print_constraint)
+                .collect::<Vec<_>>()
+                .join(", ");
+            if constraints.is_empty() {
+                format!("{keyword}.")
+            } else {
+                format!("{keyword} {constraints}.")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn print_constraint<L: Label + AsRef<str>>(constraint: &Constraint<L>) -> String {
+    match constraint {
+        Constraint::Object { label, tags } => {
+            let tags = tags
+                .iter()
+                .map(|tag| {
+                    let args = match tag {
+                        ObjectTag::Initial | ObjectTag::Terminal => vec![],
+                        ObjectTag::Product(a, b)
+                        | ObjectTag::Coproduct(a, b)
+                        | ObjectTag::Exponential(a, b)
+                        | ObjectTag::Equalizer(a, b)
+                        | ObjectTag::Coequalizer(a, b)
+                        | ObjectTag::Pullback(a, b)
+                        | ObjectTag::Pushout(a, b) => vec![a, b],
+                    };
+                    print_tag(object_tag_name(tag), args)
+                })
+                .collect::<Vec<_>>();
+            format!("{}{}", print_label(label), print_tags(tags))
+        }
+        Constraint::Morphism {
+            label,
+            connection,
+            tags,
+        } => {
+            let connection = match connection {
+                MorphismConnection::Regular { from, to } => {
+                    format!("{} -> {}", print_label(from), print_label(to))
+                }
+                MorphismConnection::Isomorphism(a, b) => {
+                    format!("{} <-> {}", print_label(a), print_label(b))
+                }
+            };
+            let tags = tags
+                .iter()
+                .map(|tag| {
+                    let args = match tag {
+                        MorphismTag::Identity(object) => vec![object],
+                        MorphismTag::Composition { first, second } => vec![first, second],
+                        MorphismTag::Isomorphism(f, g) => vec![f, g],
+                        _ => vec![],
+                    };
+                    print_tag(morphism_tag_name(tag), args)
+                })
+                .collect::<Vec<_>>();
+            format!(
+                "{}: {}{}",
+                print_morphism_label(label),
+                connection,
+                print_tags(tags)
+            )
+        }
+        Constraint::Equality(equality) => {
+            format!(
+                "{} = {}",
+                print_path(equality.left()),
+                print_path(equality.right())
+            )
+        }
+    }
+}
+
+/// Prints the composition right to left.
+fn print_path<L: AsRef<str>>(path: &[L]) -> String {
+    path.iter()
+        .rev()
+        .map(print_label)
+        .collect::<Vec<_>>()
+        .join(" ∘ ")
+}
+
+fn print_tag<L: AsRef<str>>(name: &str, args: Vec<&L>) -> String {
+    if args.is_empty() {
+        name.to_owned()
+    } else {
+        let args = args
+            .into_iter()
+            .map(print_label)
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("{name}({args})")
+    }
+}
+
+fn print_tags(tags: Vec<String>) -> String {
+    if tags.is_empty() {
+        String::new()
+    } else {
+        format!(" [{}]", tags.join(", "))
+    }
+}
+
+fn print_label<L: AsRef<str>>(label: L) -> String {
+    let label = label.as_ref();
+    if is_plain_label(label) {
+        label.to_owned()
+    } else if label.contains('"') {
+        // Enough `#` for the closing quote not to appear in the label
+        let hashes = (1..)
+            .map(|n| "#".repeat(n))
+            .find(|hashes| !label.contains(&format!("\"{hashes}")))
+            .expect("Labels are finite");
+        format!("{hashes}\"{label}\"{hashes}")
+    } else {
+        format!("\"{label}\"")
+    }
+}
+
+/// Prints the label of a morphism, which can be written as a composition.
+fn print_morphism_label<L: AsRef<str>>(label: L) -> String {
+    let label = label.as_ref();
+    let parts: Vec<_> = label.split('∘').collect();
+    if parts.len() > 1 && parts.iter().all(|part| is_plain_label(part)) {
+        label.to_owned()
+    } else {
+        print_label(label)
+    }
+}
+
+fn is_plain_label(label: &str) -> bool {
+    !label.is_empty() && label.chars().all(is_label_char) && !KEYWORDS.contains(&label)
+}
@@ -0,0 +1,183 @@
+use super::*;
+
+/// The limits of [`Category::saturate`].
+/// A rule application that would exceed one of them is undone.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SaturationBudget {
+    /// The maximum number of rounds.
+    pub max_rounds: usize,
+    /// The maximum number of objects in the category.
+    pub max_objects: usize,
+    /// The maximum number of morphisms a new morphism may be composed of.
+    pub max_path_length: usize,
+    /// The maximum nesting of products in a new object.
+    pub max_product_depth: usize,
+}
+
+/// What a round of saturation has added to the category.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SaturationRound {
+    pub objects: Vec<ObjectId>,
+    pub morphisms: Vec<MorphismId>,
+    pub equalities: Vec<Equality>,
+    /// Tags added to the objects.
+    pub object_tags: Vec<(ObjectId, Vec<ObjectTag>)>,
+    /// Tags added to the morphisms.
+    pub morphism_tags: Vec<(MorphismId, Vec<MorphismTag>)>,
+    /// The number of rule applications undone for exceeding the budget.
+    pub rejected: usize,
+}
+
+impl SaturationRound {
+    /// Whether the round has not changed the category.
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+            && self.morphisms.is_empty()
+            && self.equalities.is_empty()
+            && self.object_tags.is_empty()
+            && self.morphism_tags.is_empty()
+    }
+}
+
+pub struct Saturation<O, M, E> {
+    /// The rounds that have added something to the category.
+    pub rounds: Vec<SaturationRound>,
+    /// Whether the rules cannot add anything else,
+    /// i.e. the saturation has not been stopped by the budget.
+    pub fixpoint: bool,
+    /// The actions that undo the whole saturation.
+    pub undo: Vec<Action<O, M, E>>,
+}
+
+impl<O, M, E> Category<O, M, E> {
+    /// Applies the rules in rounds until nothing new can be added within the budget.
+    /// Each round applies the rules to everything present at its start:
+    /// rules that start with a `forall` with every binding of their input,
+    /// and rules that start with an `exists` once.
+    /// If an error occurs, the category is left unchanged.
+    pub fn saturate<L: Label>(
+        &mut self,
+        rules: &[Rule<L>],
+        budget: &SaturationBudget,
+        object_constructor: impl Fn(Vec<ObjectTag<&Object<O>, &Morphism<M>>>) -> O,
+        morphism_constructor: impl Fn(
+            MorphismConnection<&Object<O>>,
+            Vec<MorphismTag<&Object<O>, &Morphism<M>>>,
+        ) -> M,
+        equality_constructor: impl Fn(&Equality) -> E,
+    ) -> Result<Saturation<O, M, E>, ApplyError<L>> {
+        let mut saturation = Saturation {
+            rounds: Vec::new(),
+            fixpoint: false,
+            undo: Vec::new(),
+        };
+
+        for _ in 0..budget.max_rounds {
+            let mut round = SaturationRound {
+                objects: Vec::new(),
+                morphisms: Vec::new(),
+                equalities: Vec::new(),
+                object_tags: Vec::new(),
+                morphism_tags: Vec::new(),
+                rejected: 0,
+            };
+
+            let applications = match self.rule_applications(rules) {
+                Ok(applications) => applications,
+                Err(error) => {
+                    self.action_undo(saturation.undo);
+                    return Err(error);
+                }
+            };
+            for application in applications {
+                let result = self.apply_rule(
+                    &rules[application.rule],
+                    application.bindings,
+                    &object_constructor,
+                    &morphism_constructor,
+                    &equality_constructor,
+                );
+                let undo = match result {
+                    Ok((undo, true)) if !undo.is_empty() => undo,
+                    Ok(_) => continue,
+                    Err(error) => {
+                        self.action_undo(saturation.undo);
+                        return Err(error);
+                    }
+                };
+
+                let (objects, morphisms) = created_ids(&undo);
+                if !self.fits_budget(&objects, &morphisms, budget) {
+                    self.action_undo(undo);
+                    round.rejected += 1;
+                    continue;
+                }
+
+                round.objects.extend(objects);
+                round.morphisms.extend(morphisms);
+                for action in &undo {
+                    match action {
+                        Action::RemoveEqualities(equalities) => {
+                            round.equalities.extend(equalities.iter().cloned())
+                        }
+                        Action::RemoveObjectTags(tags) => {
+                            round.object_tags.extend(tags.iter().cloned())
+                        }
+                        Action::RemoveMorphismTags(tags) => {
+                            round.morphism_tags.extend(tags.iter().cloned())
+                        }
+                        _ => (),
+                    }
+                }
+                // Later applications are undone first
+                saturation.undo.splice(0..0, undo);
+            }
+
+            if round.is_empty() {
+                saturation.fixpoint = round.rejected == 0;
+                break;
+            }
+            saturation.rounds.push(round);
+        }
+
+        Ok(saturation)
+    }
+
+    fn fits_budget(
+        &self,
+        objects: &[ObjectId],
+        morphisms: &[MorphismId],
+        budget: &SaturationBudget,
+    ) -> bool {
+        self.objects.len() <= budget.max_objects
+            && objects.iter().all(|&object| {
+                self.product_depth(object, budget.max_product_depth + 1) <= budget.max_product_depth
+            })
+            && morphisms.iter().all(|&morphism| {
+                util::decompose_morphism(morphism, self).len() <= budget.max_path_length
+            })
+    }
+
+    /// Returns the nesting depth of the products, that the object is,
+    /// counting at most up to `limit`.
+    fn product_depth(&self, object: ObjectId, limit: usize) -> usize {
+        if limit == 0 {
+            return 0;
+        }
+        self.objects.get(&object).map_or(0, |object| {
+            object
+                .tags
+                .iter()
+                .filter_map(|tag| match *tag {
+                    ObjectTag::Product(a, b) => Some(
+                        1 + self
+                            .product_depth(a, limit - 1)
+                            .max(self.product_depth(b, limit - 1)),
+                    ),
+                    _ => None,
+                })
+                .max()
+                .unwrap_or(0)
+        })
+    }
+}
@@ -0,0 +1,489 @@
+use std::collections::HashMap;
+
+use super::*;
+
+/// Refers to an object or a morphism independently of the ids
+/// that get generated when the script is replayed.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ScriptRef<L> {
+    /// Present in the category before the first step, referred to by its name.
+    Initial(L),
+    /// Created by the step for the label of an exists construction of its rule,
+    /// when the construction was applied with the bindings, sorted by the label.
+    Created {
+        step: usize,
+        label: L,
+        bindings: Vec<(L, ScriptRef<L>)>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScriptStep<L> {
+    pub rule: RuleId,
+    pub objects: Vec<(L, ScriptRef<L>)>,
+    pub morphisms: Vec<(L, ScriptRef<L>)>,
+}
+
+/// A sequence of rule applications that can be replayed on another category.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofScript<L> {
+    pub steps: Vec<ScriptStep<L>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScriptError<L> {
+    /// The step refers to a rule or an inverse rule that does not exist.
+    UnknownRule {
+        step: usize,
+    },
+    /// The rule has no id, so the step cannot refer to it.
+    UnnamedRule {
+        step: usize,
+    },
+    /// The label is bound to something that is neither named
+    /// nor created by a previous step, or is not in the category.
+    UnresolvedReference {
+        step: usize,
+        label: L,
+    },
+    /// The bindings of the step do not match the input of the rule.
+    NotAnInstance {
+        step: usize,
+    },
+    Apply {
+        step: usize,
+        error: ApplyError<L>,
+    },
+}
+
+/// Records the rules applied to a category into a [`ProofScript`].
+#[derive(Debug, Clone)]
+pub struct ScriptRecorder<L> {
+    script: ProofScript<L>,
+    references: References<L>,
+}
+
+/// How the objects and the morphisms of a category are referred to by a script.
+#[derive(Debug, Clone)]
+struct References<L> {
+    objects: HashMap<ObjectId, ScriptRef<L>>,
+    morphisms: HashMap<MorphismId, ScriptRef<L>>,
+}
+
+type Refs<L> = Vec<(L, ScriptRef<L>)>;
+
+impl<L> ProofScript<L> {
+    pub fn new() -> Self {
+        Self { steps: Vec::new() }
+    }
+}
+
+impl<L> Default for ProofScript<L> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<L: Label> ProofScript<L> {
+    /// Applies the steps to the category, where the initial things
+    /// are bound to their names, and the rules are looked up by their ids.
+    /// Returns the actions that undo the changes.
+    /// If an error occurs, the category is left unchanged.
+    pub fn replay<O, M, E>(
+        &self,
+        category: &mut Category<O, M, E>,
+        initial: &Bindings<L>,
+        rules: &[Rule<L>],
+        object_constructor: impl Fn(Vec<ObjectTag<&Object<O>, &Morphism<M>>>) -> O,
+        morphism_constructor: impl Fn(
+            MorphismConnection<&Object<O>>,
+            Vec<MorphismTag<&Object<O>, &Morphism<M>>>,
+        ) -> M,
+        equality_constructor: impl Fn(&Equality) -> E,
+    ) -> Result<Vec<Action<O, M, E>>, ScriptError<L>> {
+        let mut actions = Vec::new();
+        let mut references = References::new(initial);
+        for (index, step) in self.steps.iter().enumerate() {
+            let result = replay_step(
+                index,
+                step,
+                category,
+                rules,
+                &references,
+                &object_constructor,
+                &morphism_constructor,
+                &equality_constructor,
+            );
+            let (undo, constructions) = match result {
+                Ok(result) => result,
+                Err(error) => {
+                    category.action_undo(actions);
+                    return Err(error);
+                }
+            };
+
+            references.extend(index, &constructions);
+            // Later steps are undone first
+            actions.splice(0..0, undo);
+        }
+        Ok(actions)
+    }
+
+    /// Checks that the script replays on the category,
+    /// with every step being an instance of its rule.
+    /// The category is left unchanged.
+    pub fn verify<O, M, E>(
+        &self,
+        category: &mut Category<O, M, E>,
+        initial: &Bindings<L>,
+        rules: &[Rule<L>],
+        object_constructor: impl Fn(Vec<ObjectTag<&Object<O>, &Morphism<M>>>) -> O,
+        morphism_constructor: impl Fn(
+            MorphismConnection<&Object<O>>,
+            Vec<MorphismTag<&Object<O>, &Morphism<M>>>,
+        ) -> M,
+        equality_constructor: impl Fn(&Equality) -> E,
+    ) -> Result<(), ScriptError<L>> {
+        let undo = self.replay(
+            category,
+            initial,
+            rules,
+            object_constructor,
+            morphism_constructor,
+            equality_constructor,
+        )?;
+        category.action_undo(undo);
+        Ok(())
+    }
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn replay_step<O, M, E, L: Label>(
+    index: usize,
+    step: &ScriptStep<L>,
+    category: &mut Category<O, M, E>,
+    rules: &[Rule<L>],
+    references: &References<L>,
+    object_constructor: &impl Fn(Vec<ObjectTag<&Object<O>, &Morphism<M>>>) -> O,
+    morphism_constructor: &impl Fn(
+        MorphismConnection<&Object<O>>,
+        Vec<MorphismTag<&Object<O>, &Morphism<M>>>,
+    ) -> M,
+    equality_constructor: &impl Fn(&Equality) -> E,
+) -> Result<(Vec<Action<O, M, E>>, Vec<Construction<L>>), ScriptError<L>> {
+    let bindings = references
+        .resolve(step, category)
+        .map_err(|label| ScriptError::UnresolvedReference { step: index, label })?;
+
+    let rule = rules
+        .iter()
+        .find(|rule| {
+            rule.id()
+                .is_some_and(|id| id.name == step.rule.name && id.inverse.is_none())
+        })
+        .ok_or(ScriptError::UnknownRule { step: index })?;
+    let inverse;
+    let rule = match step.rule.inverse {
+        None => rule,
+        Some(i) => {
+            inverse = rule.invert();
+            inverse
+                .get(i)
+                .ok_or(ScriptError::UnknownRule { step: index })?
+        }
+    };
+    if !category.is_rule_instance(rule, &bindings) {
+        return Err(ScriptError::NotAnInstance { step: index });
+    }
+
+    category
+        .apply_step_rule(
+            rule,
+            bindings,
+            object_constructor,
+            morphism_constructor,
+            equality_constructor,
+        )
+        .map(|(undo, _, constructions)| (undo, constructions))
+        .map_err(|error| ScriptError::Apply { step: index, error })
+}
+
+impl<O, M, E> Category<O, M, E> {
+    /// Checks that the bindings bind the whole input of the rule,
+    /// and that the bound objects and morphisms satisfy it.
+    pub fn is_rule_instance<L: Label>(&self, rule: &Rule<L>, bindings: &Bindings<L>) -> bool {
+        let input = rule.get_input();
+        let bound = input.iter().all(|constraint| match constraint {
+            Constraint::Object { label, .. } => bindings.get_object(label).is_some(),
+            Constraint::Morphism { label, .. } => bindings.get_morphism(label).is_some(),
+            Constraint::Equality(_) => true,
+        });
+        bound
+            && self
+                .find_candidates(input, bindings)
+                .ok()
+                .flatten()
+                .is_some_and(|mut candidates| candidates.next().is_some())
+    }
+
+    /// Applies the inverse rule like [`Category::apply_rule`], and then removes
+    /// the input morphisms of the inverse rule that are not part of any equality.
+    #[allow(clippy::type_complexity)]
+    pub fn apply_inverse_rule<L: Label>(
+        &mut self,
+        inverse: &Rule<L>,
+        bindings: Bindings<L>,
+        object_constructor: impl Fn(Vec<ObjectTag<&Object<O>, &Morphism<M>>>) -> O,
+        morphism_constructor: impl Fn(
+            MorphismConnection<&Object<O>>,
+            Vec<MorphismTag<&Object<O>, &Morphism<M>>>,
+        ) -> M,
+        equality_constructor: impl Fn(&Equality) -> E,
+    ) -> Result<(Vec<Action<O, M, E>>, bool), ApplyError<L>> {
+        self.apply_inverse_rule_traced(
+            inverse,
+            bindings,
+            object_constructor,
+            morphism_constructor,
+            equality_constructor,
+        )
+        .map(|(undo, applied, _)| (undo, applied))
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn apply_inverse_rule_traced<L: Label>(
+        &mut self,
+        inverse: &Rule<L>,
+        bindings: Bindings<L>,
+        object_constructor: impl Fn(Vec<ObjectTag<&Object<O>, &Morphism<M>>>) -> O,
+        morphism_constructor: impl Fn(
+            MorphismConnection<&Object<O>>,
+            Vec<MorphismTag<&Object<O>, &Morphism<M>>>,
+        ) -> M,
+        equality_constructor: impl Fn(&Equality) -> E,
+    ) -> Result<(Vec<Action<O, M, E>>, bool, Vec<Construction<L>>), ApplyError<L>> {
+        let remove: Vec<_> = inverse
+            .get_input()
+            .iter()
+            .filter_map(|constraint| match constraint {
+                Constraint::Morphism { label, .. } => bindings.get_morphism(label),
+                _ => None,
+            })
+            .collect();
+
+        let (mut undo, applied, constructions) = self.apply_rule_traced(
+            inverse,
+            bindings,
+            object_constructor,
+            morphism_constructor,
+            equality_constructor,
+        )?;
+        if applied {
+            let remove: Vec<_> = remove
+                .into_iter()
+                .filter(|&id| self.equalities.get_equalities_with(id).next().is_none())
+                .collect();
+            if !remove.is_empty() {
+                undo.extend(self.action_do_unchecked(Action::RemoveMorphisms(remove)));
+            }
+        }
+        Ok((undo, applied, constructions))
+    }
+
+    /// Applies the rule, or the inverse rule if its id says so.
+    #[allow(clippy::type_complexity)]
+    fn apply_step_rule<L: Label>(
+        &mut self,
+        rule: &Rule<L>,
+        bindings: Bindings<L>,
+        object_constructor: impl Fn(Vec<ObjectTag<&Object<O>, &Morphism<M>>>) -> O,
+        morphism_constructor: impl Fn(
+            MorphismConnection<&Object<O>>,
+            Vec<MorphismTag<&Object<O>, &Morphism<M>>>,
+        ) -> M,
+        equality_constructor: impl Fn(&Equality) -> E,
+    ) -> Result<(Vec<Action<O, M, E>>, bool, Vec<Construction<L>>), ApplyError<L>> {
+        if rule.id().is_some_and(|id| id.inverse.is_some()) {
+            self.apply_inverse_rule_traced(
+                rule,
+                bindings,
+                object_constructor,
+                morphism_constructor,
+                equality_constructor,
+            )
+        } else {
+            self.apply_rule_traced(
+                rule,
+                bindings,
+                object_constructor,
+                morphism_constructor,
+                equality_constructor,
+            )
+        }
+    }
+}
+
+impl<L: Label> References<L> {
+    fn new(initial: &Bindings<L>) -> Self {
+        Self {
+            objects: initial
+                .objects
+                .iter()
+                .map(|(label, &id)| (id, ScriptRef::Initial(label.clone())))
+                .collect(),
+            morphisms: initial
+                .morphisms
+                .iter()
+                .map(|(label, &id)| (id, ScriptRef::Initial(label.clone())))
+                .collect(),
+        }
+    }
+
+    /// Refers to the bound objects and morphisms, sorted by the label.
+    /// Fails with the label of the first thing that cannot be referred to.
+    fn refer(&self, bindings: &Bindings<L>) -> Result<(Refs<L>, Refs<L>), L> {
+        let mut objects = bindings
+            .objects
+            .iter()
+            .map(|(label, id)| match self.objects.get(id) {
+                Some(reference) => Ok((label.clone(), reference.clone())),
+                None => Err(label.clone()),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        objects.sort();
+        let mut morphisms = bindings
+            .morphisms
+            .iter()
+            .map(|(label, id)| match self.morphisms.get(id) {
+                Some(reference) => Ok((label.clone(), reference.clone())),
+                None => Err(label.clone()),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        morphisms.sort();
+        Ok((objects, morphisms))
+    }
+
+    /// Binds the labels of the step to the objects and morphisms of the category.
+    /// Fails with the label of the first reference that cannot be resolved.
+    fn resolve<O, M, E>(
+        &self,
+        step: &ScriptStep<L>,
+        category: &Category<O, M, E>,
+    ) -> Result<Bindings<L>, L> {
+        let objects: HashMap<_, _> = self
+            .objects
+            .iter()
+            .filter(|(id, _)| category.objects.contains(id))
+            .map(|(&id, reference)| (reference, id))
+            .collect();
+        let morphisms: HashMap<_, _> = self
+            .morphisms
+            .iter()
+            .filter(|(id, _)| category.morphisms.contains(id))
+            .map(|(&id, reference)| (reference, id))
+            .collect();
+
+        let mut bindings = Bindings::new();
+        for (label, reference) in &step.objects {
+            let id = objects.get(reference).ok_or_else(|| label.clone())?;
+            bindings.bind_object(label.clone(), *id);
+        }
+        for (label, reference) in &step.morphisms {
+            let id = morphisms.get(reference).ok_or_else(|| label.clone())?;
+            bindings.bind_morphism(label.clone(), *id);
+        }
+        Ok(bindings)
+    }
+
+    /// Refers to the things created by the step.
+    /// The things created with bindings that cannot be referred to are skipped.
+    fn extend(&mut self, step: usize, constructions: &[Construction<L>]) {
+        for construction in constructions {
+            let Ok((mut bindings, morphisms)) = self.refer(&construction.bindings) else {
+                continue;
+            };
+            bindings.extend(morphisms);
+            bindings.sort();
+
+            let created = |label: &L| ScriptRef::Created {
+                step,
+                label: label.clone(),
+                bindings: bindings.clone(),
+            };
+            for (label, &id) in &construction.created.objects {
+                self.objects.insert(id, created(label));
+            }
+            for (label, &id) in &construction.created.morphisms {
+                self.morphisms.insert(id, created(label));
+            }
+        }
+    }
+}
+
+impl<L: Label> ScriptRecorder<L> {
+    /// Starts recording, referring to the things initially in the category by their names.
+    pub fn new(initial: &Bindings<L>) -> Self {
+        Self {
+            script: ProofScript::new(),
+            references: References::new(initial),
+        }
+    }
+
+    pub fn script(&self) -> &ProofScript<L> {
+        &self.script
+    }
+
+    /// Applies the rule like [`Category::apply_rule`],
+    /// or like [`Category::apply_inverse_rule`] if it is an inverse rule,
+    /// and returns the step recording the application together with the undo actions.
+    /// The step becomes a part of the script once it is pushed.
+    /// Nothing is applied if the rule has no id,
+    /// or if a label is bound to something that is neither named nor created by a step.
+    #[allow(clippy::type_complexity)]
+    pub fn apply_rule<O, M, E>(
+        &mut self,
+        category: &mut Category<O, M, E>,
+        rule: &Rule<L>,
+        bindings: Bindings<L>,
+        object_constructor: impl Fn(Vec<ObjectTag<&Object<O>, &Morphism<M>>>) -> O,
+        morphism_constructor: impl Fn(
+            MorphismConnection<&Object<O>>,
+            Vec<MorphismTag<&Object<O>, &Morphism<M>>>,
+        ) -> M,
+        equality_constructor: impl Fn(&Equality) -> E,
+    ) -> Result<(Vec<Action<O, M, E>>, bool, ScriptStep<L>), ScriptError<L>> {
+        let step = self.script.steps.len();
+        let rule_id = rule.id().ok_or(ScriptError::UnnamedRule { step })?;
+        let (objects, morphisms) = self
+            .references
+            .refer(&bindings)
+            .map_err(|label| ScriptError::UnresolvedReference { step, label })?;
+        let script_step = ScriptStep {
+            rule: rule_id.clone(),
+            objects,
+            morphisms,
+        };
+
+        let (undo, applied, constructions) = category
+            .apply_step_rule(
+                rule,
+                bindings,
+                object_constructor,
+                morphism_constructor,
+                equality_constructor,
+            )
+            .map_err(|error| ScriptError::Apply { step, error })?;
+        self.references.extend(step, &constructions);
+        Ok((undo, applied, script_step))
+    }
+
+    /// Appends the step to the script.
+    pub fn push(&mut self, step: ScriptStep<L>) {
+        self.script.steps.push(step);
+    }
+
+    /// Removes the last step from the script, so that it can be pushed again.
+    pub fn pop(&mut self) -> Option<ScriptStep<L>> {
+        self.script.steps.pop()
+    }
+}
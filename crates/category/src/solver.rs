@@ -0,0 +1,260 @@
+use std::cell::OnceCell;
+use std::collections::VecDeque;
+
+use super::*;
+use crate::util::decompose_morphism;
+
+/// The maximum number of rewrite rules the solver derives by default
+/// before giving up with [`PathEquality::Unknown`].
+pub const DEFAULT_SOLVER_BUDGET: usize = 200;
+
+/// A path of morphisms in application order: `[f, g]` means `g ∘ f`.
+pub type Path = Vec<MorphismId>;
+
+/// The result of deciding whether two paths are equal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathEquality {
+    /// The paths are proven to be equal.
+    Equal,
+    /// The paths are not equal: they either connect different objects,
+    /// or have different normal forms in a complete rewriting system.
+    NotEqual,
+    /// The budget ran out before the rewriting system was completed,
+    /// and the paths could not be proven equal.
+    Unknown,
+}
+
+/// Decides the equality of paths in a category
+/// using Knuth-Bendix completion of its equalities.
+///
+/// Paths are treated as words of morphisms: compositions are decomposed,
+/// and identities are removed. Equalities are oriented to rewrite
+/// longer words into shorter ones (ties are broken by the morphism ids),
+/// and the rules are completed by resolving their critical pairs.
+/// Once the rules are complete, two paths are equal
+/// if and only if they rewrite to the same word.
+pub struct PathSolver<'a, O, M, E> {
+    category: &'a Category<O, M, E>,
+    rules: Vec<(Path, Path)>,
+    complete: bool,
+}
+
+/// A [`PathSolver`] that is only completed when it is first needed,
+/// so that a search can share one completion between all of its checks.
+pub(crate) struct LazySolver<'a, O, M, E> {
+    category: &'a Category<O, M, E>,
+    solver: OnceCell<PathSolver<'a, O, M, E>>,
+}
+
+impl<'a, O, M, E> LazySolver<'a, O, M, E> {
+    pub fn new(category: &'a Category<O, M, E>) -> Self {
+        Self {
+            category,
+            solver: OnceCell::new(),
+        }
+    }
+
+    pub fn category(&self) -> &'a Category<O, M, E> {
+        self.category
+    }
+
+    pub fn get(&self) -> &PathSolver<'a, O, M, E> {
+        self.solver
+            .get_or_init(|| self.category.path_solver(DEFAULT_SOLVER_BUDGET))
+    }
+}
+
+impl<O, M, E> Category<O, M, E> {
+    /// Completes the equalities of the category into a rewriting system,
+    /// deriving at most `budget` rules.
+    pub fn path_solver(&self, budget: usize) -> PathSolver<'_, O, M, E> {
+        PathSolver::new(self, budget)
+    }
+
+    /// Decides whether two paths are equal under the equalities of the category.
+    /// See [`PathSolver`] for details.
+    pub fn solve_paths(
+        &self,
+        left: &[MorphismId],
+        right: &[MorphismId],
+        budget: usize,
+    ) -> PathEquality {
+        if left == right {
+            return PathEquality::Equal;
+        }
+        self.path_solver(budget).solve(left, right)
+    }
+
+    /// Returns the source and the target of a composable path of regular morphisms.
+    pub(crate) fn path_endpoints(&self, path: &[MorphismId]) -> Option<(ObjectId, ObjectId)> {
+        let mut endpoints: Option<(ObjectId, ObjectId)> = None;
+        for id in path {
+            let (from, to) = match self.morphisms.get(id)?.connection {
+                MorphismConnection::Regular { from, to } => (from, to),
+                MorphismConnection::Isomorphism(..) => return None,
+            };
+            endpoints = match endpoints {
+                None => Some((from, to)),
+                Some((source, last)) if last == from => Some((source, to)),
+                Some(_) => return None,
+            };
+        }
+        endpoints
+    }
+
+    /// Decomposes compositions and removes identities.
+    /// An empty word stands for an identity.
+    pub(crate) fn path_word(&self, path: &[MorphismId]) -> Path {
+        path.iter()
+            .flat_map(|&id| decompose_morphism(id, self))
+            .filter(|id| !self.is_identity(id))
+            .collect()
+    }
+
+    pub(crate) fn is_identity(&self, id: &MorphismId) -> bool {
+        self.morphisms.get(id).is_some_and(|morphism| {
+            morphism
+                .tags
+                .iter()
+                .any(|tag| matches!(tag, MorphismTag::Identity(_)))
+        })
+    }
+}
+
+impl<'a, O, M, E> PathSolver<'a, O, M, E> {
+    fn new(category: &'a Category<O, M, E>, budget: usize) -> Self {
+        let mut solver = Self {
+            category,
+            rules: Vec::new(),
+            complete: false,
+        };
+
+        let mut pending: VecDeque<(Path, Path)> = category
+            .equalities
+            .iter_equalities()
+            .map(|equality| {
+                (
+                    category.path_word(equality.left()),
+                    category.path_word(equality.right()),
+                )
+            })
+            .collect();
+
+        while let Some((left, right)) = pending.pop_front() {
+            let left = solver.reduce(left);
+            let right = solver.reduce(right);
+            if left == right {
+                continue;
+            }
+            if solver.rules.len() >= budget {
+                return solver;
+            }
+
+            let rule = if (left.len(), &left) > (right.len(), &right) {
+                (left, right)
+            } else {
+                (right, left)
+            };
+            for other in &solver.rules {
+                pending.extend(critical_pairs(&rule, other));
+                pending.extend(critical_pairs(other, &rule));
+            }
+            pending.extend(critical_pairs(&rule, &rule));
+            solver.rules.push(rule);
+        }
+
+        solver.complete = true;
+        solver
+    }
+
+    pub fn category(&self) -> &'a Category<O, M, E> {
+        self.category
+    }
+
+    /// Whether the rewriting system is complete,
+    /// i.e. the solver never returns [`PathEquality::Unknown`].
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    /// Decides whether two paths are equal.
+    pub fn solve(&self, left: &[MorphismId], right: &[MorphismId]) -> PathEquality {
+        match (
+            self.category.path_endpoints(left),
+            self.category.path_endpoints(right),
+        ) {
+            (Some(left), Some(right)) if left == right => (),
+            _ => return PathEquality::NotEqual,
+        }
+
+        let left = self.normal_form(left);
+        let right = self.normal_form(right);
+        if left == right {
+            PathEquality::Equal
+        } else if self.complete {
+            PathEquality::NotEqual
+        } else {
+            PathEquality::Unknown
+        }
+    }
+
+    /// Returns the word the path rewrites to.
+    pub fn normal_form(&self, path: &[MorphismId]) -> Path {
+        self.reduce(self.category.path_word(path))
+    }
+
+    fn reduce(&self, mut word: Path) -> Path {
+        // Every rewrite makes the word smaller, so this terminates
+        while let Some((i, (left, right))) = self.rules.iter().find_map(|rule| {
+            (0..=word.len().saturating_sub(rule.0.len()))
+                .find(|&i| word[i..].starts_with(&rule.0))
+                .map(|i| (i, rule))
+        }) {
+            word.splice(i..i + left.len(), right.iter().copied());
+        }
+        word
+    }
+}
+
+/// Returns the critical pairs of two rules:
+/// words that can be rewritten by both rules in different ways.
+fn critical_pairs(first: &(Path, Path), second: &(Path, Path)) -> Vec<(Path, Path)> {
+    let (first_left, first_right) = first;
+    let (second_left, second_right) = second;
+    let mut pairs = Vec::new();
+
+    // A suffix of the first overlaps with a prefix of the second
+    for overlap in 1..first_left.len().min(second_left.len()) {
+        let split = first_left.len() - overlap;
+        if first_left[split..] == second_left[..overlap] {
+            let left = first_right
+                .iter()
+                .chain(&second_left[overlap..])
+                .copied()
+                .collect();
+            let right = first_left[..split]
+                .iter()
+                .chain(second_right)
+                .copied()
+                .collect();
+            pairs.push((left, right));
+        }
+    }
+
+    // The second is contained in the first
+    if first != second && second_left.len() <= first_left.len() {
+        for i in 0..=first_left.len() - second_left.len() {
+            if first_left[i..].starts_with(second_left) {
+                let right = first_left[..i]
+                    .iter()
+                    .chain(second_right)
+                    .chain(&first_left[i + second_left.len()..])
+                    .copied()
+                    .collect();
+                pairs.push((first_right.clone(), right));
+            }
+        }
+    }
+
+    pairs
+}
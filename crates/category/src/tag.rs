@@ -1,19 +1,53 @@
 use super::*;
 
-impl<O> ObjectTag<O> {
-    pub fn map<V, Fv: FnMut(O) -> V>(self, mut fv: Fv) -> ObjectTag<V> {
+impl<O, M> ObjectTag<O, M> {
+    pub fn map<V, E, Fv: FnMut(O) -> V, Fe: FnMut(M) -> E>(
+        self,
+        mut fv: Fv,
+        mut fe: Fe,
+    ) -> ObjectTag<V, E> {
         match self {
             Self::Initial => ObjectTag::Initial,
             Self::Terminal => ObjectTag::Terminal,
             Self::Product(a, b) => ObjectTag::Product(fv(a), fv(b)),
+            Self::Coproduct(a, b) => ObjectTag::Coproduct(fv(a), fv(b)),
+            Self::Exponential(b, a) => ObjectTag::Exponential(fv(b), fv(a)),
+            Self::Equalizer(f, g) => ObjectTag::Equalizer(fe(f), fe(g)),
+            Self::Coequalizer(f, g) => ObjectTag::Coequalizer(fe(f), fe(g)),
+            Self::Pullback(f, g) => ObjectTag::Pullback(fe(f), fe(g)),
+            Self::Pushout(f, g) => ObjectTag::Pushout(fe(f), fe(g)),
         }
     }
 
-    pub fn map_borrowed<V, Fv: FnMut(&O) -> V>(&self, mut fv: Fv) -> ObjectTag<V> {
+    pub fn map_borrowed<V, E, Fv: FnMut(&O) -> V, Fe: FnMut(&M) -> E>(
+        &self,
+        mut fv: Fv,
+        mut fe: Fe,
+    ) -> ObjectTag<V, E> {
         match self {
             Self::Initial => ObjectTag::Initial,
             Self::Terminal => ObjectTag::Terminal,
             Self::Product(a, b) => ObjectTag::Product(fv(a), fv(b)),
+            Self::Coproduct(a, b) => ObjectTag::Coproduct(fv(a), fv(b)),
+            Self::Exponential(b, a) => ObjectTag::Exponential(fv(b), fv(a)),
+            Self::Equalizer(f, g) => ObjectTag::Equalizer(fe(f), fe(g)),
+            Self::Coequalizer(f, g) => ObjectTag::Coequalizer(fe(f), fe(g)),
+            Self::Pullback(f, g) => ObjectTag::Pullback(fe(f), fe(g)),
+            Self::Pushout(f, g) => ObjectTag::Pushout(fe(f), fe(g)),
+        }
+    }
+
+    /// Returns the objects and the morphisms referenced by the tag.
+    pub fn references(&self) -> (Vec<&O>, Vec<&M>) {
+        match self {
+            Self::Initial | Self::Terminal => (vec![], vec![]),
+            Self::Product(a, b) | Self::Coproduct(a, b) | Self::Exponential(a, b) => {
+                (vec![a, b], vec![])
+            }
+            Self::Equalizer(f, g)
+            | Self::Coequalizer(f, g)
+            | Self::Pullback(f, g)
+            | Self::Pushout(f, g) => (vec![], vec![f, g]),
         }
     }
 }
@@ -28,6 +62,15 @@ impl<O, M> MorphismTag<O, M> {
             Self::Unique => MorphismTag::Unique,
             Self::ProductP1 => MorphismTag::ProductP1,
             Self::ProductP2 => MorphismTag::ProductP2,
+            Self::CoproductJ1 => MorphismTag::CoproductJ1,
+            Self::CoproductJ2 => MorphismTag::CoproductJ2,
+            Self::EqualizerInclusion => MorphismTag::EqualizerInclusion,
+            Self::CoequalizerProjection => MorphismTag::CoequalizerProjection,
+            Self::PullbackP1 => MorphismTag::PullbackP1,
+            Self::PullbackP2 => MorphismTag::PullbackP2,
+            Self::PushoutJ1 => MorphismTag::PushoutJ1,
+            Self::PushoutJ2 => MorphismTag::PushoutJ2,
+            Self::Evaluation => MorphismTag::Evaluation,
             Self::Identity(v) => MorphismTag::Identity(fv(v)),
             Self::Composition { first, second } => MorphismTag::Composition {
                 first: fe(first),
@@ -46,6 +89,15 @@ impl<O, M> MorphismTag<O, M> {
             Self::Unique => MorphismTag::Unique,
             Self::ProductP1 => MorphismTag::ProductP1,
             Self::ProductP2 => MorphismTag::ProductP2,
+            Self::CoproductJ1 => MorphismTag::CoproductJ1,
+            Self::CoproductJ2 => MorphismTag::CoproductJ2,
+            Self::EqualizerInclusion => MorphismTag::EqualizerInclusion,
+            Self::CoequalizerProjection => MorphismTag::CoequalizerProjection,
+            Self::PullbackP1 => MorphismTag::PullbackP1,
+            Self::PullbackP2 => MorphismTag::PullbackP2,
+            Self::PushoutJ1 => MorphismTag::PushoutJ1,
+            Self::PushoutJ2 => MorphismTag::PushoutJ2,
+            Self::Evaluation => MorphismTag::Evaluation,
             Self::Identity(v) => MorphismTag::Identity(fv(v)),
             Self::Composition { first, second } => MorphismTag::Composition {
                 first: fe(first),
@@ -54,4 +106,59 @@ impl<O, M> MorphismTag<O, M> {
             Self::Isomorphism(f, g) => MorphismTag::Isomorphism(fe(f), fe(g)),
         }
     }
+
+    /// Returns the objects and the morphisms referenced by the tag.
+    pub fn references(&self) -> (Vec<&O>, Vec<&M>) {
+        match self {
+            Self::Identity(v) => (vec![v], vec![]),
+            Self::Composition { first, second } => (vec![], vec![first, second]),
+            Self::Isomorphism(f, g) => (vec![], vec![f, g]),
+            _ => (vec![], vec![]),
+        }
+    }
+}
+
+impl<O, M> ObjectTag<O, M> {
+    /// Returns the corresponding tag in the opposite category,
+    /// or `None` if the tag has no dual.
+    pub fn dual(self) -> Option<Self> {
+        match self {
+            Self::Initial => Some(Self::Terminal),
+            Self::Terminal => Some(Self::Initial),
+            Self::Product(a, b) => Some(Self::Coproduct(a, b)),
+            Self::Coproduct(a, b) => Some(Self::Product(a, b)),
+            Self::Equalizer(f, g) => Some(Self::Coequalizer(f, g)),
+            Self::Coequalizer(f, g) => Some(Self::Equalizer(f, g)),
+            Self::Pullback(f, g) => Some(Self::Pushout(f, g)),
+            Self::Pushout(f, g) => Some(Self::Pullback(f, g)),
+            Self::Exponential(_, _) => None,
+        }
+    }
+}
+
+impl<O, M> MorphismTag<O, M> {
+    /// Returns the corresponding tag in the opposite category,
+    /// or `None` if the tag has no dual.
+    pub fn dual(self) -> Option<Self> {
+        match self {
+            Self::Identity(v) => Some(Self::Identity(v)),
+            Self::Unique => Some(Self::Unique),
+            Self::Composition { first, second } => Some(Self::Composition {
+                first: second,
+                second: first,
+            }),
+            Self::Isomorphism(f, g) => Some(Self::Isomorphism(f, g)),
+            Self::ProductP1 => Some(Self::CoproductJ1),
+            Self::ProductP2 => Some(Self::CoproductJ2),
+            Self::CoproductJ1 => Some(Self::ProductP1),
+            Self::CoproductJ2 => Some(Self::ProductP2),
+            Self::EqualizerInclusion => Some(Self::CoequalizerProjection),
+            Self::CoequalizerProjection => Some(Self::EqualizerInclusion),
+            Self::PullbackP1 => Some(Self::PushoutJ1),
+            Self::PullbackP2 => Some(Self::PushoutJ2),
+            Self::PushoutJ1 => Some(Self::PullbackP1),
+            Self::PushoutJ2 => Some(Self::PullbackP2),
+            Self::Evaluation => None,
+        }
+    }
 }
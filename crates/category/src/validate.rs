@@ -0,0 +1,229 @@
+use super::*;
+
+/// A broken invariant of a category, found by [`Category::validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvariantViolation {
+    /// The morphism connects an object that is not in the category.
+    DanglingEndPoint {
+        morphism: MorphismId,
+        object: ObjectId,
+    },
+    /// The tag refers to something that is not in the category.
+    DanglingObjectTag { object: ObjectId, tag: ObjectTag },
+    /// The tag refers to something that is not in the category.
+    DanglingMorphismTag {
+        morphism: MorphismId,
+        tag: MorphismTag,
+    },
+    /// The equality refers to a morphism that is not in the category.
+    DanglingEquality { equality: Equality },
+    /// The identity is not a loop on the tagged object.
+    IdentityNotLoop {
+        morphism: MorphismId,
+        object: ObjectId,
+    },
+    /// The parts of the composition do not chain,
+    /// or do not lead between the end points of the morphism.
+    BadComposition {
+        morphism: MorphismId,
+        first: MorphismId,
+        second: MorphismId,
+    },
+    /// The morphisms of the isomorphism do not compose into loops.
+    BadIsomorphism {
+        morphism: MorphismId,
+        f: MorphismId,
+        g: MorphismId,
+    },
+    /// The projection does not lead from a product to the corresponding factor.
+    BadProjection { morphism: MorphismId },
+    /// The injection does not lead from the corresponding summand to a coproduct.
+    BadInjection { morphism: MorphismId },
+    /// The sides of the equality are not composable paths between the same objects.
+    NonComposableEquality { equality: Equality },
+}
+
+impl<O, M, E> Category<O, M, E> {
+    /// Checks the invariants of the category and reports every violation.
+    pub fn validate(&self) -> Result<(), Vec<InvariantViolation>> {
+        let mut violations = Vec::new();
+
+        let mut objects: Vec<_> = self.objects.iter().collect();
+        objects.sort_by_key(|(&id, _)| id);
+        for (&id, object) in objects {
+            for tag in &object.tags {
+                if !self.references_exist(tag.references()) {
+                    violations.push(InvariantViolation::DanglingObjectTag {
+                        object: id,
+                        tag: *tag,
+                    });
+                }
+            }
+        }
+
+        let mut morphisms: Vec<_> = self.morphisms.iter().collect();
+        morphisms.sort_by_key(|(&id, _)| id);
+        for (&id, morphism) in morphisms {
+            let mut dangling = false;
+            for &object in morphism.connection.end_points() {
+                if !self.objects.contains(&object) {
+                    dangling = true;
+                    violations.push(InvariantViolation::DanglingEndPoint {
+                        morphism: id,
+                        object,
+                    });
+                }
+            }
+            for tag in &morphism.tags {
+                if !self.references_exist(tag.references()) {
+                    violations.push(InvariantViolation::DanglingMorphismTag {
+                        morphism: id,
+                        tag: *tag,
+                    });
+                } else if !dangling {
+                    violations.extend(self.check_morphism_tag(id, morphism, tag));
+                }
+            }
+        }
+
+        let mut equalities: Vec<_> = self.equalities.iter_equalities().collect();
+        equalities.sort();
+        for equality in equalities {
+            let sides = [equality.left(), equality.right()];
+            if sides
+                .iter()
+                .any(|side| side.iter().any(|id| !self.morphisms.contains(id)))
+            {
+                violations.push(InvariantViolation::DanglingEquality {
+                    equality: equality.clone(),
+                });
+                continue;
+            }
+            let [left, right] = sides.map(|side| self.path_ends(side));
+            if !left.iter().any(|ends| right.contains(ends)) {
+                violations.push(InvariantViolation::NonComposableEquality {
+                    equality: equality.clone(),
+                });
+            }
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+
+    /// Panics if the category is invalid. Does nothing in release builds.
+    pub(crate) fn debug_validate(&self) {
+        if cfg!(debug_assertions) {
+            if let Err(violations) = self.validate() {
+                panic!("The category is invalid: {violations:?}");
+            }
+        }
+    }
+
+    fn references_exist(&self, (objects, morphisms): (Vec<&ObjectId>, Vec<&MorphismId>)) -> bool {
+        objects.into_iter().all(|id| self.objects.contains(id))
+            && morphisms.into_iter().all(|id| self.morphisms.contains(id))
+    }
+
+    fn check_morphism_tag(
+        &self,
+        id: MorphismId,
+        morphism: &Morphism<M>,
+        tag: &MorphismTag,
+    ) -> Option<InvariantViolation> {
+        let directions = directions(&morphism.connection);
+        match *tag {
+            MorphismTag::Identity(object) => {
+                let [from, to] = morphism.connection.end_points();
+                (*from != object || *to != object).then_some(InvariantViolation::IdentityNotLoop {
+                    morphism: id,
+                    object,
+                })
+            }
+            MorphismTag::Composition { first, second } => {
+                let ends = self.path_ends(&[first, second]);
+                (!directions.iter().any(|direction| ends.contains(direction))).then_some(
+                    InvariantViolation::BadComposition {
+                        morphism: id,
+                        first,
+                        second,
+                    },
+                )
+            }
+            MorphismTag::Isomorphism(f, g) => {
+                let loops =
+                    |path: &[MorphismId]| self.path_ends(path).iter().any(|(from, to)| from == to);
+                (!loops(&[f, g]) || !loops(&[g, f])).then_some(InvariantViolation::BadIsomorphism {
+                    morphism: id,
+                    f,
+                    g,
+                })
+            }
+            MorphismTag::ProductP1 | MorphismTag::ProductP2 => {
+                let first = matches!(tag, MorphismTag::ProductP1);
+                let valid = directions.iter().any(|(from, to)| {
+                    self.objects.get(from).is_some_and(|object| {
+                        object.tags.iter().any(|tag| match *tag {
+                            ObjectTag::Product(a, b) => *to == if first { a } else { b },
+                            _ => false,
+                        })
+                    })
+                });
+                (!valid).then_some(InvariantViolation::BadProjection { morphism: id })
+            }
+            MorphismTag::CoproductJ1 | MorphismTag::CoproductJ2 => {
+                let first = matches!(tag, MorphismTag::CoproductJ1);
+                let valid = directions.iter().any(|(from, to)| {
+                    self.objects.get(to).is_some_and(|object| {
+                        object.tags.iter().any(|tag| match *tag {
+                            ObjectTag::Coproduct(a, b) => *from == if first { a } else { b },
+                            _ => false,
+                        })
+                    })
+                });
+                (!valid).then_some(InvariantViolation::BadInjection { morphism: id })
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the possible pairs of the start and the end of the path,
+    /// or nothing if the morphisms do not compose.
+    /// Isomorphisms may be passed in either direction.
+    pub(crate) fn path_ends(&self, path: &[MorphismId]) -> Vec<(ObjectId, ObjectId)> {
+        let mut ends: Option<Vec<(ObjectId, ObjectId)>> = None;
+        for id in path {
+            let Some(morphism) = self.morphisms.get(id) else {
+                return vec![];
+            };
+            let directions = directions(&morphism.connection);
+            ends = Some(match ends {
+                None => directions,
+                Some(ends) => ends
+                    .into_iter()
+                    .flat_map(|(start, end)| {
+                        directions
+                            .iter()
+                            .filter(move |(from, _)| *from == end)
+                            .map(move |&(_, to)| (start, to))
+                    })
+                    .collect(),
+            });
+        }
+        let mut ends = ends.unwrap_or_default();
+        ends.sort();
+        ends.dedup();
+        ends
+    }
+}
+
+/// The directions in which the morphism can be passed.
+fn directions(connection: &MorphismConnection) -> Vec<(ObjectId, ObjectId)> {
+    match *connection {
+        MorphismConnection::Regular { from, to } => vec![(from, to)],
+        MorphismConnection::Isomorphism(a, b) => vec![(a, b), (b, a)],
+    }
+}
@@ -0,0 +1,66 @@
+use category::constraint::ConstraintsBuilder;
+use category::prelude::*;
+use category::{axioms, ApplyError, Bindings, CategoryBuilder};
+
+#[test]
+fn test_stale_bindings() {
+    let rule_product = axioms::rule_product::<&str>().unwrap();
+
+    let mut category = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .build();
+    let object_a = *category.objects.iter().next().unwrap().0;
+
+    // An id from a different category
+    let other = CategoryBuilder::<(), (), (), &str>::new()
+        .object("X", vec![], ())
+        .object("Y", vec![], ())
+        .build();
+    let stale = other
+        .objects
+        .iter()
+        .map(|(&id, _)| id)
+        .find(|&id| !category.objects.contains(&id))
+        .unwrap();
+
+    let bindings = Bindings::from_objects(vec![("A", object_a), ("B", stale)]);
+    let constraints = ConstraintsBuilder::new()
+        .object("A", vec![])
+        .object("B", vec![])
+        .build();
+    assert_eq!(
+        Some(ApplyError::UnknownObject {
+            label: "B",
+            id: stale
+        }),
+        category.find_candidates(&constraints, &bindings).err()
+    );
+
+    let result = category.apply_rule(&rule_product, bindings, |_| (), |_, _| (), |_| ());
+    assert_eq!(
+        Some(ApplyError::UnknownObject {
+            label: "B",
+            id: stale
+        }),
+        result.err()
+    );
+    // The category is left unchanged
+    assert_eq!(1, category.objects.len());
+    assert_eq!(0, category.morphisms.len());
+}
+
+#[test]
+fn test_unbound_label() {
+    let mut category = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .build();
+
+    // The product tag refers to objects that are not bound
+    let constraints = ConstraintsBuilder::new()
+        .object("AxB", vec![ObjectTag::Product("A", "B")])
+        .build();
+    let result =
+        category.apply_constraints(&constraints, &Bindings::new(), &|_| (), &|_, _| (), |_| ());
+    assert_eq!(Some(ApplyError::UnboundObject { label: "A" }), result.err());
+    assert_eq!(1, category.objects.len());
+}
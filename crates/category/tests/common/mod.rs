@@ -0,0 +1,6 @@
+/// Collects exactly `N` ids in sorted order, i.e. in the order they were created.
+pub fn ids<T: Ord, const N: usize>(ids: impl IntoIterator<Item = T>) -> [T; N] {
+    let mut ids = ids.into_iter().collect::<Vec<_>>();
+    ids.sort();
+    ids.try_into().ok().expect("Unexpected number of ids")
+}
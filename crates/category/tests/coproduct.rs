@@ -0,0 +1,206 @@
+use category::constraint::ConstraintsBuilder;
+use category::prelude::*;
+use category::{axioms, Bindings, Rule};
+
+use std::fmt::Debug;
+
+#[test]
+fn test_coproduct() {
+    // Build the initial category
+    let mut category = Category::new();
+
+    let object_a = category.new_object(Object {
+        tags: vec![],
+        inner: (),
+    });
+    let object_b = category.new_object(Object {
+        tags: vec![],
+        inner: (),
+    });
+    let bindings = Bindings::from_objects(vec![("A", object_a), ("B", object_b)]);
+
+    // Get rules
+    let rule_coproduct = axioms::rule_coproduct::<&str>().unwrap();
+    let rule_initial = axioms::rule_initial::<&str>().unwrap();
+
+    // Apply coproduct rule
+    category
+        .apply_rule(&rule_coproduct, bindings.clone(), |_| (), |_, _| (), |_| ())
+        .unwrap();
+    print_category(&category);
+    assert_eq!(3, category.objects.len());
+    assert_eq!(3, category.morphisms.len());
+    assert_eq!(0, category.equalities.len());
+
+    // Injections go into the coproduct
+    let constraints = ConstraintsBuilder::new()
+        .object("A+B", vec![ObjectTag::Coproduct("A", "B")])
+        .morphism("j1", "A", "A+B", vec![MorphismTag::CoproductJ1])
+        .morphism("j2", "B", "A+B", vec![MorphismTag::CoproductJ2])
+        .build();
+    let candidates = category
+        .find_candidates(&constraints, &bindings)
+        .unwrap()
+        .unwrap()
+        .count();
+    assert_eq!(1, candidates);
+
+    // Apply coproduct rule again
+    category
+        .apply_rule(&rule_coproduct, bindings, |_| (), |_, _| (), |_| ())
+        .unwrap();
+    print_category(&category);
+    assert_eq!(3, category.objects.len());
+    assert_eq!(3, category.morphisms.len());
+    assert_eq!(0, category.equalities.len());
+
+    // Apply initial rule
+    let result = category
+        .apply_rule(
+            &rule_initial,
+            Bindings::single_object("A", object_a),
+            |_| (),
+            |_, _| (),
+            |_| (),
+        )
+        .unwrap();
+    assert!(result.1);
+    print_category(&category);
+    assert_eq!(4, category.objects.len());
+    assert_eq!(4, category.morphisms.len());
+
+    let constraints = ConstraintsBuilder::new()
+        .object("0", vec![ObjectTag::Initial])
+        .morphism("m", "0", "A", vec![MorphismTag::Unique])
+        .build();
+    let candidates = category
+        .find_candidates(&constraints, &Bindings::single_object("A", object_a))
+        .unwrap()
+        .unwrap()
+        .count();
+    assert_eq!(1, candidates);
+}
+
+#[test]
+fn test_coproduct_initial() {
+    // A+0 ≃ A
+    let mut category = Category::new();
+    let object_a = category.new_object(Object {
+        tags: vec![],
+        inner: (),
+    });
+
+    let rule_identity = axioms::rule_identity::<&str>().unwrap();
+    let rule_composition = axioms::rule_composition::<&str>().unwrap();
+    let rule_initial = axioms::rule_initial::<&str>().unwrap();
+    let rule_coproduct = axioms::rule_coproduct::<&str>().unwrap();
+    let rule_unique = axioms::rule_unique::<&str>().unwrap();
+    let rule_isomorphism = axioms::rule_isomorphism::<&str>().unwrap();
+
+    // The unique morphism u: 0 -> A, and the identity of A
+    apply(
+        &mut category,
+        &rule_initial,
+        Bindings::single_object("A", object_a),
+    );
+    apply(
+        &mut category,
+        &rule_identity,
+        Bindings::single_object("A", object_a),
+    );
+    let object_0 = find_object(&category, |tag| *tag == ObjectTag::Initial);
+    let u = find_morphism(&category, object_0, object_a);
+
+    // The coproduct A+0 and the copairing m = [id_A, u]: A+0 -> A
+    let summands = Bindings::from_objects(vec![("A", object_a), ("B", object_0)]);
+    apply(&mut category, &rule_coproduct, summands.clone());
+    let object_sum = find_object(&category, |tag| {
+        *tag == ObjectTag::Coproduct(object_a, object_0)
+    });
+    let j1 = find_morphism(&category, object_a, object_sum);
+    let m = find_morphism(&category, object_sum, object_a);
+
+    // j1 ∘ u = j2, as both are morphisms from the initial object
+    apply(
+        &mut category,
+        &rule_composition,
+        Bindings::from_morphisms(vec![("f", u), ("g", j1)]),
+    );
+    apply(
+        &mut category,
+        &rule_initial,
+        Bindings::single_object("A", object_sum),
+    );
+    apply(&mut category, &rule_unique, Bindings::new());
+
+    // j1 ∘ m = id, as both are copairings of j1 and j2
+    apply(
+        &mut category,
+        &rule_composition,
+        Bindings::from_morphisms(vec![("f", m), ("g", j1)]),
+    );
+    apply(&mut category, &rule_coproduct, summands);
+
+    // So j1 and m are inverse to each other
+    apply(
+        &mut category,
+        &rule_isomorphism,
+        Bindings::from_morphisms(vec![("f", j1), ("g", m)]),
+    );
+    print_category(&category);
+    assert!(category.morphisms.iter().any(|(_, morphism)| {
+        morphism.connection == MorphismConnection::Isomorphism(object_a, object_sum)
+            && morphism.tags == vec![MorphismTag::Isomorphism(j1, m)]
+    }));
+}
+
+fn apply(category: &mut Category<(), (), ()>, rule: &Rule<&str>, bindings: Bindings<&str>) {
+    category
+        .apply_rule(rule, bindings, |_| (), |_, _| (), |_| ())
+        .unwrap();
+}
+
+/// Finds the only object with a matching tag.
+fn find_object(category: &Category<(), (), ()>, tag: impl Fn(&ObjectTag) -> bool) -> ObjectId {
+    let found: Vec<_> = category
+        .objects
+        .iter()
+        .filter(|(_, object)| object.tags.iter().any(&tag))
+        .map(|(&id, _)| id)
+        .collect();
+    assert_eq!(found.len(), 1, "Expected a single object");
+    found[0]
+}
+
+/// Finds the only morphism between the objects.
+fn find_morphism(category: &Category<(), (), ()>, from: ObjectId, to: ObjectId) -> MorphismId {
+    let found: Vec<_> = category
+        .morphisms
+        .iter()
+        .filter(|(_, morphism)| morphism.connection == MorphismConnection::Regular { from, to })
+        .map(|(&id, _)| id)
+        .collect();
+    assert_eq!(found.len(), 1, "Expected a single morphism");
+    found[0]
+}
+
+fn print_category<O: Debug, M: Debug, E: Debug>(category: &Category<O, M, E>) {
+    println!("\n----- Category -----");
+    println!("Objects:");
+    for (id, object) in category.objects.iter() {
+        println!("{:4} - {:?}", id.raw(), object)
+    }
+    println!("Morphisms:");
+    for (id, morphism) in category.morphisms.iter() {
+        println!("{:4} - {:?}", id.raw(), morphism)
+    }
+    println!("Equalities:");
+    for (equality, inner) in category.equalities.iter() {
+        println!(
+            "  {:?} = {:?}: {inner:?}",
+            equality.left(),
+            equality.right()
+        );
+    }
+    println!("");
+}
@@ -0,0 +1,123 @@
+use category::prelude::*;
+use category::{
+    Bindings, CategoryBuilder, Constraint, Constraints, CountermodelBudget, CountermodelOutcome,
+    Equality, UnsupportedTag,
+};
+
+mod common;
+use common::ids;
+
+const BUDGET: CountermodelBudget = CountermodelBudget {
+    max_size: 2,
+    max_models: 10_000,
+};
+
+/// Builds the parallel morphisms `f, g: A -> B`, equal if `equal`.
+fn parallel(equal: bool) -> Category<(), (), ()> {
+    let builder = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .morphism("f", "A", "B", vec![], ())
+        .morphism("g", "A", "B", vec![], ());
+    if equal {
+        builder.equality(["f"], ["g"], ()).build()
+    } else {
+        builder.build()
+    }
+}
+
+/// Turns the category into goal constraints,
+/// bound to the things with the same ids in the facts.
+fn to_goal(goal: &Category<(), (), ()>) -> (Constraints<CategoryThing>, Bindings<CategoryThing>) {
+    let label = |&id: &MorphismId| CategoryThing::Morphism { id };
+    let mut constraints = goal.to_constraints();
+    constraints.extend(goal.equalities.iter_equalities().map(|equality| {
+        Constraint::Equality(
+            Equality::new(
+                equality.left().iter().map(label).collect(),
+                equality.right().iter().map(label).collect(),
+            )
+            .unwrap(),
+        )
+    }));
+
+    let mut bindings = Bindings::new();
+    for (&id, _) in goal.objects.iter() {
+        bindings.bind_object(CategoryThing::Object { id }, id);
+    }
+    for (&id, _) in goal.morphisms.iter() {
+        bindings.bind_morphism(CategoryThing::Morphism { id }, id);
+    }
+    (constraints, bindings)
+}
+
+#[test]
+fn test_countermodel_found() {
+    let facts = parallel(false);
+    let (goal, bindings) = to_goal(&parallel(true));
+    let [f, g] = ids(facts.morphisms.iter().map(|(&id, _)| id));
+
+    let outcome = facts.find_countermodel(&goal, &bindings, &BUDGET).unwrap();
+    let CountermodelOutcome::Found(model) = outcome else {
+        panic!("Expected a countermodel, found {outcome:?}");
+    };
+    assert_eq!(facts.check_model(&model), Ok(()));
+    assert_ne!(model.morphisms[&f], model.morphisms[&g]);
+}
+
+#[test]
+fn test_countermodel_not_found() {
+    let facts = parallel(true);
+    let (goal, bindings) = to_goal(&parallel(true));
+    assert_eq!(
+        facts.find_countermodel(&goal, &bindings, &BUDGET).unwrap(),
+        CountermodelOutcome::NotFound
+    );
+
+    // Morphisms into a terminal object are equal in every model
+    let facts = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("1", vec![ObjectTag::Terminal], ())
+        .morphism("f", "A", "1", vec![], ())
+        .morphism("g", "A", "1", vec![], ())
+        .build();
+    let (goal, bindings) = to_goal(
+        &CategoryBuilder::<(), (), (), &str>::new()
+            .object("A", vec![], ())
+            .object("1", vec![ObjectTag::Terminal], ())
+            .morphism("f", "A", "1", vec![], ())
+            .morphism("g", "A", "1", vec![], ())
+            .equality(["f"], ["g"], ())
+            .build(),
+    );
+    assert_eq!(
+        facts.find_countermodel(&goal, &bindings, &BUDGET).unwrap(),
+        CountermodelOutcome::NotFound
+    );
+
+    let budget = CountermodelBudget {
+        max_size: 2,
+        max_models: 1,
+    };
+    assert_eq!(
+        facts.find_countermodel(&goal, &bindings, &budget).unwrap(),
+        CountermodelOutcome::OutOfBudget
+    );
+}
+
+#[test]
+fn test_countermodel_unsupported() {
+    let facts = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .object("A+B", vec![ObjectTag::Coproduct("A", "B")], ())
+        .build();
+    let [a, b, coproduct] = ids(facts.objects.iter().map(|(&id, _)| id));
+    assert_eq!(
+        facts.find_countermodel(&Vec::new(), &Bindings::new(), &BUDGET),
+        Err(UnsupportedTag::Object {
+            object: coproduct,
+            tag: ObjectTag::Coproduct(a, b),
+        })
+    );
+}
@@ -0,0 +1,119 @@
+use category::constraint::ConstraintsBuilder;
+use category::prelude::*;
+use category::{axioms, Bindings, CategoryBuilder};
+
+#[test]
+fn test_dual_rule() {
+    // Dual of the product is the coproduct
+    let mut category = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .build();
+    let mut objects = category
+        .objects
+        .iter()
+        .map(|(&id, _)| id)
+        .collect::<Vec<_>>();
+    objects.sort();
+    let bindings = Bindings::from_objects(vec![("A", objects[0]), ("B", objects[1])]);
+
+    let rule_coproduct = axioms::rule_product::<&str>().unwrap().dual().unwrap();
+    let result = category
+        .apply_rule(&rule_coproduct, bindings.clone(), |_| (), |_, _| (), |_| ())
+        .unwrap();
+    assert!(result.1);
+    assert_eq!(3, category.objects.len());
+    assert_eq!(3, category.morphisms.len());
+    assert_eq!(0, category.equalities.len());
+
+    let constraints = ConstraintsBuilder::new()
+        .object("A+B", vec![ObjectTag::Coproduct("A", "B")])
+        .morphism("j1", "A", "A+B", vec![MorphismTag::CoproductJ1])
+        .morphism("j2", "B", "A+B", vec![MorphismTag::CoproductJ2])
+        .build();
+    let candidates = category
+        .find_candidates(&constraints, &bindings)
+        .unwrap()
+        .unwrap()
+        .count();
+    assert_eq!(1, candidates);
+}
+
+#[test]
+fn test_opposite() {
+    let category = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![ObjectTag::Initial], ())
+        .object("B", vec![], ())
+        .object("C", vec![], ())
+        .morphism("f", "A", "B", vec![], ())
+        .morphism("g", "B", "C", vec![], ())
+        .morphism(
+            "g o f",
+            "A",
+            "C",
+            vec![MorphismTag::Composition {
+                first: "f",
+                second: "g",
+            }],
+            (),
+        )
+        .morphism("h", "A", "C", vec![], ())
+        .equality(vec!["f", "g"], vec!["h"], ())
+        .build();
+
+    let opposite = category.opposite().unwrap();
+    assert_eq!(category.objects.len(), opposite.objects.len());
+    assert_eq!(category.morphisms.len(), opposite.morphisms.len());
+    assert_eq!(category.equalities.len(), opposite.equalities.len());
+
+    for (id, object) in category.objects.iter() {
+        let dual = opposite.objects.get(id).unwrap();
+        let tags: Option<Vec<_>> = object.tags.iter().map(|tag| tag.dual()).collect();
+        assert_eq!(Some(dual.tags.clone()), tags);
+    }
+    for (id, morphism) in category.morphisms.iter() {
+        let dual = opposite.morphisms.get(id).unwrap();
+        assert_eq!(morphism.connection.dual(), dual.connection);
+        for tag in &dual.tags {
+            if let &MorphismTag::Composition { first, second } = tag {
+                // Composition order is reversed
+                let first = opposite.morphisms.get(&first).unwrap();
+                let second = opposite.morphisms.get(&second).unwrap();
+                assert_eq!(
+                    first.connection.end_points()[1],
+                    second.connection.end_points()[0]
+                );
+            }
+        }
+    }
+    for equality in category.equalities.iter_equalities() {
+        assert!(opposite
+            .equalities
+            .contains_equality(&equality.clone().dual()));
+    }
+
+    // Taking the opposite twice gives back the original category
+    let original = opposite.opposite().unwrap();
+    for (id, morphism) in category.morphisms.iter() {
+        let same = original.morphisms.get(id).unwrap();
+        assert_eq!(morphism.connection, same.connection);
+        assert_eq!(morphism.tags, same.tags);
+    }
+    for equality in category.equalities.iter_equalities() {
+        assert!(original.equalities.contains_equality(equality));
+    }
+}
+
+#[test]
+fn test_no_dual() {
+    // Exponentials have no dual, so there is nothing sound to construct
+    let rule = axioms::rule_exponential::<&str>().unwrap();
+    assert!(rule.dual().is_none());
+
+    let category = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .object("B^A", vec![ObjectTag::Exponential("A", "B")], ())
+        .build();
+    assert!(category.opposite().is_none());
+}
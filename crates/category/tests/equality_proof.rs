@@ -0,0 +1,130 @@
+use category::prelude::*;
+use category::{CategoryBuilder, Rewrite};
+
+mod common;
+use common::ids;
+
+#[test]
+fn test_equality_proof() {
+    //   x     y     w
+    // A --> B --> C --> D
+    // z = y ∘ x: A -> C
+    // v = w ∘ y: B -> D
+    let category = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .object("C", vec![], ())
+        .object("D", vec![], ())
+        .morphism("id", "A", "A", vec![MorphismTag::Identity("A")], ())
+        .morphism("x", "A", "B", vec![], ())
+        .morphism("y", "B", "C", vec![], ())
+        .morphism("w", "C", "D", vec![], ())
+        .morphism("z", "A", "C", vec![], ())
+        .morphism("v", "B", "D", vec![], ())
+        .equality(vec!["x", "y"], vec!["z"], ())
+        .equality(vec!["y", "w"], vec!["v"], ())
+        .build();
+    let [id, x, y, w, z, v] = ids(category.morphisms.iter().map(|(&id, _)| id));
+
+    let left = [id, z, w];
+    let right = [x, v];
+    let proof = category
+        .prove_equality(&left, &right, 100)
+        .expect("The paths are equal");
+    assert!(proof.verify(&category, &left, &right));
+
+    // The identity is removed, then both equalities are used
+    assert_eq!(
+        proof.left[0].rewrite,
+        Rewrite::RemoveIdentity {
+            position: 0,
+            morphism: id
+        }
+    );
+    let equalities = proof
+        .left
+        .iter()
+        .chain(&proof.right)
+        .filter(|step| matches!(step.rewrite, Rewrite::Equality { .. }))
+        .count();
+    assert_eq!(equalities, 2);
+
+    // The proof does not justify other paths
+    assert!(!proof.verify(&category, &[z, w], &right));
+
+    // Not equal
+    assert_eq!(None, category.prove_equality(&[x, y], &[x, v], 100));
+    // Not enough budget
+    assert_eq!(None, category.prove_equality(&left, &right, 2));
+}
+
+#[test]
+fn test_equality_proof_composition() {
+    let category = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .object("C", vec![], ())
+        .morphism("f", "A", "B", vec![], ())
+        .morphism("g", "B", "C", vec![], ())
+        .morphism(
+            "g∘f",
+            "A",
+            "C",
+            vec![MorphismTag::Composition {
+                first: "f",
+                second: "g",
+            }],
+            (),
+        )
+        .build();
+    let [f, g, gf] = ids(category.morphisms.iter().map(|(&id, _)| id));
+
+    let proof = category
+        .prove_equality(&[gf], &[f, g], 10)
+        .expect("The paths are equal");
+    assert!(proof.verify(&category, &[gf], &[f, g]));
+    assert_eq!(
+        proof.left,
+        vec![category::ProofStep {
+            rewrite: Rewrite::Decompose {
+                position: 0,
+                morphism: gf
+            },
+            result: vec![f, g],
+        }]
+    );
+    assert!(proof.right.is_empty());
+}
+
+#[test]
+fn test_equality_proof_swapped() {
+    // h = g∘f is equal to k
+    let category = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .object("C", vec![], ())
+        .morphism("f", "A", "B", vec![], ())
+        .morphism("g", "B", "C", vec![], ())
+        .morphism(
+            "h",
+            "A",
+            "C",
+            vec![MorphismTag::Composition {
+                first: "f",
+                second: "g",
+            }],
+            (),
+        )
+        .morphism("k", "A", "C", vec![], ())
+        .equality(["h"], ["k"], ())
+        .build();
+    let [f, g, _, k] = ids(category.morphisms.iter().map(|(&id, _)| id));
+
+    // Only one of the sides can be rewritten
+    for (left, right) in [(vec![k], vec![f, g]), (vec![f, g], vec![k])] {
+        let proof = category
+            .prove_equality(&left, &right, 10)
+            .expect("The paths are equal");
+        assert!(proof.verify(&category, &left, &right));
+    }
+}
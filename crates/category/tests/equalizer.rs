@@ -0,0 +1,91 @@
+use category::constraint::ConstraintsBuilder;
+use category::prelude::*;
+use category::{axioms, Bindings, CategoryBuilder};
+
+#[test]
+fn test_equalizer() {
+    let mut category = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .morphism("f", "A", "B", vec![], ())
+        .morphism("g", "A", "B", vec![], ())
+        .build();
+    let bindings = bind_parallel(&category);
+
+    let rule_equalizer = axioms::rule_equalizer::<&str>().unwrap();
+    let result = category
+        .apply_rule(&rule_equalizer, bindings.clone(), |_| (), |_, _| (), |_| ())
+        .unwrap();
+    assert!(result.1);
+    assert_eq!(3, category.objects.len());
+    assert_eq!(4, category.morphisms.len());
+    assert_eq!(1, category.equalities.len());
+
+    let constraints = ConstraintsBuilder::new()
+        .object("E", vec![ObjectTag::Equalizer("f", "g")])
+        .morphism("e", "E", "A", vec![MorphismTag::EqualizerInclusion])
+        .equality(vec!["e", "f"], vec!["e", "g"])
+        .build();
+    let candidates = category
+        .find_candidates(&constraints, &bindings)
+        .unwrap()
+        .unwrap()
+        .count();
+    assert_eq!(1, candidates);
+
+    // Undo
+    for action in result.0 {
+        category.action_do(action);
+    }
+    assert_eq!(2, category.objects.len());
+    assert_eq!(2, category.morphisms.len());
+    assert_eq!(0, category.equalities.len());
+}
+
+#[test]
+fn test_coequalizer() {
+    let mut category = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .morphism("f", "A", "B", vec![], ())
+        .morphism("g", "A", "B", vec![], ())
+        .build();
+    let bindings = bind_parallel(&category);
+
+    let rule_coequalizer = axioms::rule_coequalizer::<&str>().unwrap();
+    let result = category
+        .apply_rule(
+            &rule_coequalizer,
+            bindings.clone(),
+            |_| (),
+            |_, _| (),
+            |_| (),
+        )
+        .unwrap();
+    assert!(result.1);
+    assert_eq!(3, category.objects.len());
+    assert_eq!(4, category.morphisms.len());
+    assert_eq!(1, category.equalities.len());
+
+    let constraints = ConstraintsBuilder::new()
+        .object("Q", vec![ObjectTag::Coequalizer("f", "g")])
+        .morphism("q", "B", "Q", vec![MorphismTag::CoequalizerProjection])
+        .equality(vec!["f", "q"], vec!["g", "q"])
+        .build();
+    let candidates = category
+        .find_candidates(&constraints, &bindings)
+        .unwrap()
+        .unwrap()
+        .count();
+    assert_eq!(1, candidates);
+}
+
+fn bind_parallel(category: &Category<(), (), ()>) -> Bindings<&'static str> {
+    let mut morphisms = category
+        .morphisms
+        .iter()
+        .map(|(&id, _)| id)
+        .collect::<Vec<_>>();
+    morphisms.sort();
+    Bindings::from_morphisms(vec![("f", morphisms[0]), ("g", morphisms[1])])
+}
@@ -0,0 +1,96 @@
+use category::constraint::ConstraintsBuilder;
+use category::prelude::*;
+use category::{axioms, Bindings, CategoryBuilder};
+
+#[test]
+fn test_exponential() {
+    let mut category = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .build();
+    let bindings = bind_objects(&category, &["A", "B"]);
+
+    let rule_exponential = axioms::rule_exponential::<&str>().unwrap();
+    let result = category
+        .apply_rule(
+            &rule_exponential,
+            bindings.clone(),
+            |_| (),
+            |_, _| (),
+            |_| (),
+        )
+        .unwrap();
+    assert!(result.1);
+    assert_eq!(4, category.objects.len());
+    assert_eq!(5, category.morphisms.len());
+    assert_eq!(0, category.equalities.len());
+
+    let constraints = ConstraintsBuilder::new()
+        .object("B^A", vec![ObjectTag::Exponential("B", "A")])
+        .object("B^AxA", vec![ObjectTag::Product("B^A", "A")])
+        .morphism("eval", "B^AxA", "B", vec![MorphismTag::Evaluation])
+        .build();
+    let candidates = category
+        .find_candidates(&constraints, &bindings)
+        .unwrap()
+        .unwrap()
+        .count();
+    assert_eq!(1, candidates);
+}
+
+#[test]
+fn test_curry() {
+    let mut category = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .object("C", vec![], ())
+        .object("CxA", vec![ObjectTag::Product("C", "A")], ())
+        .morphism("q1", "CxA", "C", vec![MorphismTag::ProductP1], ())
+        .morphism("q2", "CxA", "A", vec![MorphismTag::ProductP2], ())
+        .morphism("f", "CxA", "B", vec![], ())
+        .build();
+    let bindings = bind_objects(&category, &["A", "B", "C"]);
+
+    let rule_exponential = axioms::rule_exponential::<&str>().unwrap();
+    let result = category
+        .apply_rule(
+            &rule_exponential,
+            bindings.clone(),
+            |_| (),
+            |_, _| (),
+            |_| (),
+        )
+        .unwrap();
+    assert!(result.1);
+
+    // There is a curried morphism C -> B^A such that f factors through eval
+    let constraints = ConstraintsBuilder::new()
+        .object("B^A", vec![ObjectTag::Exponential("B", "A")])
+        .object("B^AxA", vec![ObjectTag::Product("B^A", "A")])
+        .morphism("eval", "B^AxA", "B", vec![MorphismTag::Evaluation])
+        .object("CxA", vec![ObjectTag::Product("C", "A")])
+        .morphism("λf", "C", "B^A", vec![])
+        .morphism("λf x id", "CxA", "B^AxA", vec![])
+        .morphism("f", "CxA", "B", vec![])
+        .equality(vec!["λf x id", "eval"], vec!["f"])
+        .build();
+    let candidates = category
+        .find_candidates(&constraints, &bindings)
+        .unwrap()
+        .unwrap()
+        .count();
+    assert_eq!(1, candidates);
+}
+
+fn bind_objects(
+    category: &Category<(), (), ()>,
+    labels: &[&'static str],
+) -> Bindings<&'static str> {
+    let mut objects = category
+        .objects
+        .iter()
+        .map(|(&id, _)| id)
+        .collect::<Vec<_>>();
+    objects.sort();
+    Bindings::from_objects(labels.iter().copied().zip(objects))
+}
@@ -29,6 +29,7 @@ fn test_find() {
     let candidates = category
         .find_candidates(&constraints, &Bindings::new())
         .unwrap()
+        .unwrap()
         .collect::<Vec<_>>();
 
     println!("Candidates for:");
@@ -40,3 +41,71 @@ fn test_find() {
 
     assert_eq!(candidates.len(), 1);
 }
+
+#[test]
+fn test_find_order() {
+    let mut category = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .object("AxB", vec![ObjectTag::Product("A", "B")], ())
+        .object("C", vec![], ())
+        .morphism("p1", "AxB", "A", vec![MorphismTag::ProductP1], ())
+        .morphism("p2", "AxB", "B", vec![MorphismTag::ProductP2], ())
+        .morphism("f", "C", "A", vec![], ())
+        .morphism("g", "C", "B", vec![], ())
+        .morphism("m", "C", "AxB", vec![], ())
+        .equality(vec!["m", "p1"], vec!["f"], ())
+        .equality(vec!["m", "p2"], vec!["g"], ())
+        .build();
+
+    // Unrelated objects and morphisms
+    let noise = (0..50)
+        .map(|_| {
+            category.new_object(Object {
+                tags: vec![],
+                inner: (),
+            })
+        })
+        .collect::<Vec<_>>();
+    for pair in noise.windows(2) {
+        for _ in 0..2 {
+            category.new_morphism(Morphism {
+                connection: MorphismConnection::Regular {
+                    from: pair[0],
+                    to: pair[1],
+                },
+                tags: vec![],
+                inner: (),
+            });
+        }
+    }
+
+    // Equalities are written first, but should be checked last
+    let constraints = ConstraintsBuilder::<&str>::new()
+        .equality(vec!["m", "p1"], vec!["f"])
+        .equality(vec!["m", "p2"], vec!["g"])
+        .morphism("m", "C", "AxB", vec![])
+        .morphism("f", "C", "A", vec![])
+        .morphism("g", "C", "B", vec![])
+        .morphism("p1", "AxB", "A", vec![MorphismTag::ProductP1])
+        .morphism("p2", "AxB", "B", vec![MorphismTag::ProductP2])
+        .object("AxB", vec![ObjectTag::Product("A", "B")])
+        .build();
+    let candidates = category
+        .find_candidates(&constraints, &Bindings::new())
+        .unwrap()
+        .unwrap()
+        .collect::<Vec<_>>();
+    assert_eq!(candidates.len(), 1);
+
+    let mut reversed = constraints.clone();
+    reversed.reverse();
+    let reversed_candidates = category
+        .find_candidates(&reversed, &Bindings::new())
+        .unwrap()
+        .unwrap()
+        .collect::<Vec<_>>();
+    assert_eq!(candidates.len(), reversed_candidates.len());
+    assert_eq!(candidates[0].morphisms, reversed_candidates[0].morphisms);
+    assert_eq!(candidates[0].objects, reversed_candidates[0].objects);
+}
@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+
+use category::prelude::*;
+use category::{CategoryBuilder, Equality, FinSetModel, ModelViolation};
+
+mod common;
+use common::ids;
+
+#[test]
+fn test_finset_product() {
+    let category = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .object("AxB", vec![ObjectTag::Product("A", "B")], ())
+        .morphism("p1", "AxB", "A", vec![MorphismTag::ProductP1], ())
+        .morphism("p2", "AxB", "B", vec![MorphismTag::ProductP2], ())
+        .morphism("id", "A", "A", vec![MorphismTag::Identity("A")], ())
+        .morphism(
+            "id o p1",
+            "AxB",
+            "A",
+            vec![MorphismTag::Composition {
+                first: "p1",
+                second: "id",
+            }],
+            (),
+        )
+        .equality(["p1", "id"], ["p1"], ())
+        .build();
+    let [a, b, product] = ids(category.objects.iter().map(|(&id, _)| id));
+    let [p1, p2, id, id_p1] = ids(category.morphisms.iter().map(|(&id, _)| id));
+
+    let mut model = FinSetModel {
+        objects: HashMap::from([(a, 2), (b, 3), (product, 6)]),
+        morphisms: HashMap::from([
+            (p1, (0..6).map(|x| x / 3).collect()),
+            (p2, (0..6).map(|x| x % 3).collect()),
+            (id, vec![0, 1]),
+            (id_p1, (0..6).map(|x| x / 3).collect()),
+        ]),
+    };
+    assert_eq!(category.check_model(&model), Ok(()));
+
+    // The projections do not tell the elements apart
+    model.morphisms.insert(p2, (0..6).map(|x| x % 2).collect());
+    assert_eq!(
+        category.check_model(&model),
+        Err(ModelViolation::Product {
+            object: product,
+            element: Some(2),
+        })
+    );
+    model.morphisms.insert(p2, (0..6).map(|x| x % 3).collect());
+
+    model.morphisms.insert(id, vec![1, 0]);
+    assert_eq!(
+        category.check_model(&model),
+        Err(ModelViolation::Identity {
+            morphism: id,
+            element: 0,
+        })
+    );
+    model.morphisms.insert(id, vec![0, 1]);
+
+    model.morphisms.insert(id_p1, vec![0, 0, 0, 1, 1, 0]);
+    assert_eq!(
+        category.check_model(&model),
+        Err(ModelViolation::Composition {
+            morphism: id_p1,
+            element: 5,
+        })
+    );
+    model
+        .morphisms
+        .insert(id_p1, (0..6).map(|x| x / 3).collect());
+
+    model.objects.insert(product, 5);
+    assert_eq!(
+        category.check_model(&model),
+        Err(ModelViolation::NotAFunction { morphism: p1 })
+    );
+}
+
+#[test]
+fn test_finset_equality() {
+    let category = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .object("1", vec![ObjectTag::Terminal], ())
+        .morphism("f", "A", "B", vec![], ())
+        .morphism("g", "A", "B", vec![], ())
+        .equality(["f"], ["g"], ())
+        .build();
+    let [a, b, terminal] = ids(category.objects.iter().map(|(&id, _)| id));
+    let [f, g] = ids(category.morphisms.iter().map(|(&id, _)| id));
+
+    assert_eq!(
+        category.check_model(&FinSetModel::new()),
+        Err(ModelViolation::MissingObject { object: a })
+    );
+
+    let mut model = FinSetModel {
+        objects: HashMap::from([(a, 2), (b, 2), (terminal, 1)]),
+        morphisms: HashMap::from([(f, vec![0, 1]), (g, vec![0, 0])]),
+    };
+    assert_eq!(
+        category.check_model(&model),
+        Err(ModelViolation::Equality {
+            equality: Equality::new(vec![f], vec![g]).unwrap(),
+            object: a,
+            element: 1,
+        })
+    );
+
+    model.morphisms.insert(g, vec![0, 1]);
+    assert_eq!(category.check_model(&model), Ok(()));
+
+    model.objects.insert(terminal, 2);
+    assert_eq!(
+        category.check_model(&model),
+        Err(ModelViolation::Terminal { object: terminal })
+    );
+}
+
+#[test]
+fn test_finset_isomorphism() {
+    let category = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .isomorphism("f", "A", "B", vec![], ())
+        .morphism("g", "B", "A", vec![], ())
+        .equality(["f", "g"], ["f", "g"], ())
+        .build();
+    let [a, b] = ids(category.objects.iter().map(|(&id, _)| id));
+    let [f, g] = ids(category.morphisms.iter().map(|(&id, _)| id));
+
+    let mut model = FinSetModel {
+        objects: HashMap::from([(a, 2), (b, 2)]),
+        morphisms: HashMap::from([(f, vec![1, 0]), (g, vec![0, 0])]),
+    };
+    assert_eq!(category.check_model(&model), Ok(()));
+    // Passed backwards from `B`, the isomorphism uses the inverse function
+    assert_eq!(category.eval_path(&model, &[f], b, 0), Some(1));
+    assert_eq!(category.eval_path(&model, &[g, f], b, 1), Some(1));
+
+    model.morphisms.insert(f, vec![1, 1]);
+    assert_eq!(
+        category.check_model(&model),
+        Err(ModelViolation::NotBijective { morphism: f })
+    );
+}
@@ -0,0 +1,125 @@
+use category::prelude::*;
+use category::{axioms, format, Action, Bindings, CategoryBuilder, Rule};
+
+#[test]
+fn test_category_format() {
+    let mut category = CategoryBuilder::<String, String, String, &str>::new()
+        .object("A", vec![], "A".to_owned())
+        .object("B", vec![], "B".to_owned())
+        .object("AxB", vec![ObjectTag::Product("A", "B")], "AxB".to_owned())
+        .morphism("f", "A", "B", vec![], "f".to_owned())
+        .morphism("g", "B", "A", vec![], "g".to_owned())
+        .morphism(
+            "g o f",
+            "A",
+            "A",
+            vec![MorphismTag::Composition {
+                first: "f",
+                second: "g",
+            }],
+            "g o f".to_owned(),
+        )
+        .morphism(
+            "id",
+            "A",
+            "A",
+            vec![MorphismTag::Identity("A")],
+            "id".to_owned(),
+        )
+        .equality(vec!["f", "g"], vec!["id"], "eq".to_owned())
+        .build();
+    // Leave a gap in the ids
+    let removed = category.new_object(Object {
+        tags: vec![],
+        inner: "removed".to_owned(),
+    });
+    category.remove_object(removed);
+
+    let text = format::to_string(&category).unwrap();
+    let mut loaded: Category<String, String, String> = format::from_str(&text).unwrap();
+    assert_eq!(text, format::to_string(&loaded).unwrap());
+
+    assert_eq!(category.objects.len(), loaded.objects.len());
+    assert_eq!(category.morphisms.len(), loaded.morphisms.len());
+    assert_eq!(category.equalities.len(), loaded.equalities.len());
+    for (id, object) in category.objects.iter() {
+        let other = loaded.objects.get(id).unwrap();
+        assert_eq!(object.tags, other.tags);
+        assert_eq!(object.inner, other.inner);
+    }
+    for (id, morphism) in category.morphisms.iter() {
+        let other = loaded.morphisms.get(id).unwrap();
+        assert_eq!(morphism.connection, other.connection);
+        assert_eq!(morphism.tags, other.tags);
+        assert_eq!(morphism.inner, other.inner);
+    }
+    for (equality, inner) in category.equalities.iter() {
+        assert!(loaded.equalities.contains_equality(equality));
+        assert_eq!(
+            Some(inner),
+            loaded
+                .equalities
+                .iter()
+                .find(|(other, _)| *other == equality)
+                .map(|(_, inner)| inner)
+        );
+    }
+
+    // Id counters are preserved
+    let new_object = |inner: &str| Object {
+        tags: vec![],
+        inner: inner.to_owned(),
+    };
+    assert_eq!(
+        category.new_object(new_object("C")),
+        loaded.new_object(new_object("C"))
+    );
+}
+
+#[test]
+fn test_rule_format() {
+    for rule in axioms::rule_axioms::<String>() {
+        let text = format::to_string(&rule).unwrap();
+        let loaded: Rule<String> = format::from_str(&text).unwrap();
+        assert_eq!(text, format::to_string(&loaded).unwrap());
+    }
+}
+
+#[test]
+fn test_action_format() {
+    let mut category = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .build();
+    let (actions, applied) = category
+        .apply_rule(
+            &axioms::rule_product::<&str>().unwrap(),
+            Bindings::new(),
+            |_| (),
+            |_, _| (),
+            |_| (),
+        )
+        .unwrap();
+    assert!(applied);
+
+    let text = format::to_string(&actions).unwrap();
+    let loaded: Vec<Action<(), (), ()>> = format::from_str(&text).unwrap();
+    assert_eq!(text, format::to_string(&loaded).unwrap());
+
+    // Undo using the loaded actions
+    for action in loaded {
+        category.action_do(action);
+    }
+    assert_eq!(2, category.objects.len());
+    assert_eq!(0, category.morphisms.len());
+    assert_eq!(0, category.equalities.len());
+}
+
+#[test]
+fn test_format_version() {
+    let text = r#"{ "version": 0, "data": null }"#;
+    assert!(matches!(
+        format::from_str::<Category<(), (), ()>>(text),
+        Err(format::FormatError::UnsupportedVersion(0))
+    ));
+}
@@ -0,0 +1,198 @@
+use category::prelude::*;
+use category::{
+    CategoryBuilder, Commutativity, Functor, FunctorError, NaturalTransformation, NaturalityError,
+};
+
+mod common;
+use common::ids;
+
+#[test]
+fn test_functor() {
+    // A -f-> B
+    let source = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .morphism("id_a", "A", "A", vec![MorphismTag::Identity("A")], ())
+        .morphism("id_b", "B", "B", vec![MorphismTag::Identity("B")], ())
+        .morphism("f", "A", "B", vec![], ())
+        .morphism(
+            "f o id",
+            "A",
+            "B",
+            vec![MorphismTag::Composition {
+                first: "id_a",
+                second: "f",
+            }],
+            (),
+        )
+        .build();
+    let [a, b] = ids(source.objects.iter().map(|(&id, _)| id));
+    let [id_a, id_b, f, f_id] = ids(source.morphisms.iter().map(|(&id, _)| id));
+
+    // X with a non-identity endomorphism g
+    let target = CategoryBuilder::<(), (), (), &str>::new()
+        .object("X", vec![], ())
+        .morphism("id_x", "X", "X", vec![MorphismTag::Identity("X")], ())
+        .morphism("g", "X", "X", vec![], ())
+        .build();
+    let [x] = ids(target.objects.iter().map(|(&id, _)| id));
+    let [id_x, g] = ids(target.morphisms.iter().map(|(&id, _)| id));
+
+    // Collapse everything into X
+    let mut functor = Functor::new();
+    functor.map_object(a, x);
+    functor.map_object(b, x);
+    functor.map_morphism(id_a, id_x);
+    functor.map_morphism(id_b, id_x);
+    functor.map_morphism(f, g);
+    functor.map_morphism(f_id, g);
+    assert_eq!(Ok(()), functor.check(&source, &target));
+
+    // Map an identity to a non-identity
+    functor.map_morphism(id_a, g);
+    let errors = functor.check(&source, &target).unwrap_err();
+    assert!(errors.contains(&FunctorError::Identity {
+        morphism: id_a,
+        object: a
+    }));
+    assert!(errors.contains(&FunctorError::Composition {
+        morphism: f_id,
+        first: id_a,
+        second: f,
+    }));
+
+    // Forget a morphism
+    let mut functor = Functor::new();
+    functor.map_object(a, x);
+    functor.map_object(b, x);
+    functor.map_morphism(id_a, id_x);
+    functor.map_morphism(id_b, id_x);
+    functor.map_morphism(f, g);
+    assert_eq!(
+        Err(vec![FunctorError::UnmappedMorphism(f_id)]),
+        functor.check(&source, &target)
+    );
+}
+
+#[test]
+fn test_functor_undecided() {
+    let source = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .morphism("p", "A", "A", vec![], ())
+        .morphism("q", "A", "A", vec![], ())
+        .equality(vec!["p", "q", "q", "p"], vec!["q", "p", "p", "q"], ())
+        .build();
+    let [a] = ids(source.objects.iter().map(|(&id, _)| id));
+    let [p, q] = ids(source.morphisms.iter().map(|(&id, _)| id));
+    let [equality] = ids(source.equalities.iter_equalities().cloned());
+
+    // The braid relation has no finite complete rewriting system
+    let target = CategoryBuilder::<(), (), (), &str>::new()
+        .object("X", vec![], ())
+        .morphism("a", "X", "X", vec![], ())
+        .morphism("b", "X", "X", vec![], ())
+        .equality(vec!["a", "b", "a"], vec!["b", "a", "b"], ())
+        .build();
+    let [x] = ids(target.objects.iter().map(|(&id, _)| id));
+    let [a_x, b_x] = ids(target.morphisms.iter().map(|(&id, _)| id));
+
+    let mut functor = Functor::new();
+    functor.map_object(a, x);
+    functor.map_morphism(p, a_x);
+    functor.map_morphism(q, b_x);
+    assert_eq!(
+        Err(vec![FunctorError::UndecidedEquality(equality)]),
+        functor.check(&source, &target)
+    );
+}
+
+#[test]
+fn test_naturality() {
+    // A -f-> B
+    let source = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .morphism("f", "A", "B", vec![], ())
+        .build();
+    let [a, b] = ids(source.objects.iter().map(|(&id, _)| id));
+    let [f] = ids(source.morphisms.iter().map(|(&id, _)| id));
+
+    // The naturality square
+    let build_target = |commutes: bool| {
+        let builder = CategoryBuilder::<(), (), (), &str>::new()
+            .object("FA", vec![], ())
+            .object("FB", vec![], ())
+            .object("GA", vec![], ())
+            .object("GB", vec![], ())
+            .morphism("Ff", "FA", "FB", vec![], ())
+            .morphism("Gf", "GA", "GB", vec![], ())
+            .morphism("alpha_a", "FA", "GA", vec![], ())
+            .morphism("alpha_b", "FB", "GB", vec![], ());
+        if commutes {
+            builder
+                .equality(vec!["alpha_a", "Gf"], vec!["Ff", "alpha_b"], ())
+                .build()
+        } else {
+            builder.build()
+        }
+    };
+    let target = build_target(true);
+    let [fa, fb, ga, gb] = ids(target.objects.iter().map(|(&id, _)| id));
+    let [ff, gf, alpha_a, alpha_b] = ids(target.morphisms.iter().map(|(&id, _)| id));
+
+    let mut functor_f = Functor::new();
+    functor_f.map_object(a, fa);
+    functor_f.map_object(b, fb);
+    functor_f.map_morphism(f, ff);
+    assert_eq!(Ok(()), functor_f.check(&source, &target));
+
+    let mut functor_g = Functor::new();
+    functor_g.map_object(a, ga);
+    functor_g.map_object(b, gb);
+    functor_g.map_morphism(f, gf);
+    assert_eq!(Ok(()), functor_g.check(&source, &target));
+
+    let mut alpha = NaturalTransformation::new();
+    alpha.set_component(a, alpha_a);
+    alpha.set_component(b, alpha_b);
+    assert_eq!(
+        vec![(f, Ok(Commutativity::Commutes))],
+        alpha.check(&source, &target, &functor_f, &functor_g)
+    );
+
+    // Without the equality the paths around the square are different
+    let target = build_target(false);
+    assert_eq!(
+        vec![(f, Ok(Commutativity::Refuted))],
+        alpha.check(&source, &target, &functor_f, &functor_g)
+    );
+
+    // Components going in the wrong direction
+    assert_eq!(
+        vec![(
+            f,
+            Err(NaturalityError::Component {
+                object: a,
+                component: alpha_a
+            })
+        )],
+        alpha.check(&source, &target, &functor_g, &functor_f)
+    );
+
+    // A missing component
+    let mut beta = NaturalTransformation::new();
+    beta.set_component(a, alpha_a);
+    assert_eq!(
+        vec![(f, Err(NaturalityError::MissingComponent(b)))],
+        beta.check(&source, &target, &functor_f, &functor_g)
+    );
+
+    // A functor forgetting the morphism
+    let mut functor_h = Functor::new();
+    functor_h.map_object(a, ga);
+    functor_h.map_object(b, gb);
+    assert_eq!(
+        vec![(f, Err(NaturalityError::UnmappedMorphism(f)))],
+        alpha.check(&source, &target, &functor_f, &functor_h)
+    );
+}
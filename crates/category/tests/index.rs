@@ -0,0 +1,61 @@
+use category::prelude::*;
+use category::CategoryBuilder;
+
+fn sorted<T: Ord>(iter: impl IntoIterator<Item = T>) -> Vec<T> {
+    let mut items: Vec<_> = iter.into_iter().collect();
+    items.sort();
+    items
+}
+
+#[test]
+fn test_endpoint_indexes() {
+    let mut category = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .build();
+    let [a, b] = {
+        let ids = sorted(category.objects.iter().map(|(&id, _)| id));
+        [ids[0], ids[1]]
+    };
+    let morphism = |from, to| Morphism {
+        connection: MorphismConnection::Regular { from, to },
+        tags: vec![],
+        inner: (),
+    };
+
+    let f = category.new_morphism(morphism(a, b)).unwrap();
+    let g = category.new_morphism(morphism(a, b)).unwrap();
+    let h = category.new_morphism(morphism(b, a)).unwrap();
+    let id = category.new_morphism(morphism(a, a)).unwrap();
+
+    let morphisms = &category.morphisms;
+    assert_eq!(sorted([f, g, id]), sorted(morphisms.outgoing(a)));
+    assert_eq!(sorted([h, id]), sorted(morphisms.incoming(a)));
+    assert_eq!(sorted([f, g]), sorted(morphisms.between(a, b)));
+    assert_eq!(sorted([f, g, h, id]), sorted(morphisms.connected(a)));
+    assert_eq!(sorted([a, b, b, b]), sorted(category.neighbours(a)));
+
+    // Removal
+    category.remove_morphism(g);
+    assert_eq!(vec![f], sorted(category.morphisms.between(a, b)));
+
+    // Replacing a morphism with a different connection
+    category.insert_morphism(morphism(b, b), f).unwrap();
+    assert!(category.morphisms.between(a, b).next().is_none());
+    assert_eq!(vec![f], sorted(category.morphisms.between(b, b)));
+
+    // Retain
+    category.morphisms.retain(|&morphism, _| morphism != h);
+    assert_eq!(vec![id], sorted(category.morphisms.incoming(a)));
+
+    // Opposite category reverses the indexes
+    category.insert_morphism(morphism(a, b), g).unwrap();
+    let opposite = category.opposite().unwrap();
+    assert_eq!(vec![g], sorted(opposite.morphisms.between(b, a)));
+    assert!(opposite.morphisms.between(a, b).next().is_none());
+
+    // Removing an object removes the connected morphisms from the indexes
+    category.remove_object(a);
+    assert_eq!(vec![f], sorted(category.morphisms.connected(b)));
+    assert!(category.morphisms.connected(a).next().is_none());
+}
@@ -0,0 +1,105 @@
+use category::constraint::ConstraintsBuilder;
+use category::prelude::*;
+use category::{Bindings, CategoryBuilder, RuleConstruction};
+
+mod common;
+use common::ids;
+
+#[test]
+fn test_lemma_product_symmetry() {
+    // AxB ≃ BxA, given A and B
+    let goal = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .object("AxB", vec![ObjectTag::Product("A", "B")], ())
+        .object("BxA", vec![ObjectTag::Product("B", "A")], ())
+        .isomorphism("iso", "AxB", "BxA", vec![], ())
+        .build();
+    let [a, b, _, _] = ids(goal.objects.iter().map(|(&id, _)| id));
+    let given = [
+        CategoryThing::Object { id: a },
+        CategoryThing::Object { id: b },
+    ];
+    let lemma = goal
+        .to_lemma(|thing| given.contains(&thing), |thing| thing)
+        .unwrap();
+
+    let statement = lemma.get_statement();
+    assert_eq!(statement.len(), 2);
+    assert!(
+        matches!(&statement[0], RuleConstruction::Forall(constraints) if constraints.len() == 2)
+    );
+    assert!(
+        matches!(&statement[1], RuleConstruction::Exists(constraints) if constraints.len() == 3)
+    );
+
+    // Apply the lemma in another category
+    let mut fact = CategoryBuilder::<(), (), (), &str>::new()
+        .object("X", vec![], ())
+        .object("Y", vec![], ())
+        .build();
+    let [x, y] = ids(fact.objects.iter().map(|(&id, _)| id));
+    let bindings = Bindings::from_objects([
+        (CategoryThing::Object { id: a }, x),
+        (CategoryThing::Object { id: b }, y),
+    ]);
+    fact.apply_rule(&lemma, bindings, |_| (), |_, _| (), |_| ())
+        .unwrap();
+
+    let constraints = ConstraintsBuilder::<&str>::new()
+        .object("XxY", vec![ObjectTag::Product("X", "Y")])
+        .object("YxX", vec![ObjectTag::Product("Y", "X")])
+        .isomorphism("iso", "XxY", "YxX", vec![])
+        .build();
+    assert!(fact
+        .find_candidates(&constraints, &Bindings::from_objects([("X", x), ("Y", y)]))
+        .unwrap()
+        .unwrap()
+        .next()
+        .is_some());
+}
+
+#[test]
+fn test_lemma_layers() {
+    let goal = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .object("C", vec![], ())
+        .object("AxB", vec![ObjectTag::Product("A", "B")], ())
+        .morphism("f", "A", "C", vec![], ())
+        .morphism("p1", "AxB", "A", vec![MorphismTag::ProductP1], ())
+        .morphism(
+            "f∘p1",
+            "AxB",
+            "C",
+            vec![MorphismTag::Composition {
+                first: "p1",
+                second: "f",
+            }],
+            (),
+        )
+        .build();
+    let [_, b, _, _] = ids(goal.objects.iter().map(|(&id, _)| id));
+    let [f, _, _] = ids(goal.morphisms.iter().map(|(&id, _)| id));
+    let given = [
+        CategoryThing::Object { id: b },
+        CategoryThing::Morphism { id: f },
+    ];
+    let lemma = goal
+        .to_lemma(|thing| given.contains(&thing), |thing| thing)
+        .unwrap();
+
+    // The composition refers to p1, so it comes after it
+    let statement = lemma.get_statement();
+    assert_eq!(statement.len(), 3);
+    let RuleConstruction::Forall(forall) = &statement[0] else {
+        panic!("Expected the given things first");
+    };
+    assert_eq!(forall.len(), 4);
+    assert!(
+        matches!(&statement[2], RuleConstruction::Exists(constraints) if constraints.len() == 1)
+    );
+
+    // Nothing new
+    assert!(goal.to_lemma(|_| true, |thing| thing).is_err());
+}
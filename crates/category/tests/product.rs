@@ -31,7 +31,9 @@ fn test_product() {
     let rule_composition = axioms::rule_composition::<&str>().unwrap();
 
     // Apply product rule
-    category.apply_rule(&rule_product, bindings.clone(), |_| (), |_, _| (), |_| ());
+    category
+        .apply_rule(&rule_product, bindings.clone(), |_| (), |_, _| (), |_| ())
+        .unwrap();
     print_category(&category);
     assert_eq!(3, category.objects.len());
     assert_eq!(3, category.morphisms.len());
@@ -45,6 +47,7 @@ fn test_product() {
     let morphism_f = category
         .find_candidates(&constraints, &bindings)
         .unwrap()
+        .unwrap()
         .next()
         .unwrap()
         .get_morphism(&"m")
@@ -56,6 +59,7 @@ fn test_product() {
     let morphism_g = category
         .find_candidates(&constraints, &bindings)
         .unwrap()
+        .unwrap()
         .next()
         .unwrap()
         .get_morphism(&"m")
@@ -65,20 +69,24 @@ fn test_product() {
     println!("g = {morphism_g:?}");
 
     // Apply composition rule
-    category.apply_rule(
-        &rule_composition,
-        Bindings::from_morphisms(vec![("f", morphism_f), ("g", morphism_g)]),
-        |_| (),
-        |_, _| (),
-        |_| (),
-    );
+    category
+        .apply_rule(
+            &rule_composition,
+            Bindings::from_morphisms(vec![("f", morphism_f), ("g", morphism_g)]),
+            |_| (),
+            |_, _| (),
+            |_| (),
+        )
+        .unwrap();
     print_category(&category);
     assert_eq!(3, category.objects.len());
     assert_eq!(4, category.morphisms.len());
     assert_eq!(0, category.equalities.len());
 
     // Apply product rule
-    category.apply_rule(&rule_product, bindings, |_| (), |_, _| (), |_| ());
+    category
+        .apply_rule(&rule_product, bindings, |_| (), |_, _| (), |_| ())
+        .unwrap();
     print_category(&category);
     assert_eq!(3, category.objects.len());
     assert_eq!(4, category.morphisms.len());
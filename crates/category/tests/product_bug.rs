@@ -17,7 +17,9 @@ fn test_bug() {
     });
 
     // Test product AxA
-    let result = category.apply_rule(&rule_product, Bindings::new(), |_| (), |_, _| (), |_| ());
+    let result = category
+        .apply_rule(&rule_product, Bindings::new(), |_| (), |_, _| (), |_| ())
+        .unwrap();
     assert!(result.1);
     print_category(&category);
     assert_eq!(2, category.objects.len());
@@ -168,7 +170,9 @@ fn test_bug() {
 
     // Test bug
     let bindings = Bindings::from_objects([("A", object_a), ("B", object_1)]);
-    let result = category.apply_rule(&rule_product, bindings, |_| (), |_, _| (), |_| ());
+    let result = category
+        .apply_rule(&rule_product, bindings, |_| (), |_, _| (), |_| ())
+        .unwrap();
     assert!(result.1);
 
     print_category(&category);
@@ -0,0 +1,86 @@
+use category::{axioms, prelude::CategoryThing, Bindings, CategoryBuilder, RuleId};
+
+#[test]
+fn test_provenance_records() {
+    let mut category = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .build();
+    let ids: Vec<_> = category.objects.iter().map(|(&id, _)| id).collect();
+    let (a, b) = (ids[0].min(ids[1]), ids[0].max(ids[1]));
+
+    let rule = axioms::rule_product::<&str>().unwrap();
+    let bindings = Bindings::from_objects([("A", a), ("B", b)]);
+    let (_, applied) = category
+        .apply_rule(&rule, bindings, |_| (), |_, _| (), |_| ())
+        .unwrap();
+    assert!(applied);
+
+    let derivations = category.provenance.derivations();
+    assert_eq!(derivations.len(), 1);
+    let derivation = &derivations[0];
+    assert_eq!(derivation.step, 0);
+    assert_eq!(derivation.rule, Some(RuleId::new("product")));
+    assert_eq!(
+        derivation.bindings,
+        vec![
+            ("A".to_owned(), CategoryThing::Object { id: a }),
+            ("B".to_owned(), CategoryThing::Object { id: b }),
+        ]
+    );
+    assert_eq!(derivation.objects.len(), 1);
+    assert_eq!(derivation.morphisms.len(), 3);
+
+    let product = derivation.objects[0];
+    assert_eq!(
+        category.provenance.object_origin(product).map(|d| d.step),
+        Some(0)
+    );
+    assert!(category.provenance.object_origin(a).is_none());
+    for &morphism in &derivation.morphisms {
+        assert_eq!(category.provenance.morphism(morphism).count(), 1);
+    }
+
+    let rule = axioms::rule_identity::<&str>().unwrap();
+    let (_, applied) = category
+        .apply_rule(
+            &rule,
+            Bindings::single_object("A", a),
+            |_| (),
+            |_, _| (),
+            |_| (),
+        )
+        .unwrap();
+    assert!(applied);
+    let derivation = &category.provenance.derivations()[1];
+    assert_eq!(derivation.step, 1);
+    assert_eq!(derivation.rule, Some(RuleId::new("identity")));
+    assert_eq!(derivation.morphisms.len(), 1);
+}
+
+#[test]
+fn test_provenance_undo() {
+    let mut category = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .build();
+    let rule = axioms::rule_identity::<&str>().unwrap();
+
+    let (undo, applied) = category
+        .apply_rule(&rule, Bindings::new(), |_| (), |_, _| (), |_| ())
+        .unwrap();
+    assert!(applied);
+    let recorded = category.provenance.derivations().to_vec();
+    assert_eq!(recorded.len(), 1);
+
+    let redo: Vec<_> = undo
+        .into_iter()
+        .flat_map(|action| category.action_do(action))
+        .collect();
+    assert!(category.provenance.derivations().is_empty());
+    assert_eq!(category.provenance.next_step(), 0);
+
+    for action in redo {
+        category.action_do(action);
+    }
+    assert_eq!(category.provenance.derivations(), recorded.as_slice());
+}
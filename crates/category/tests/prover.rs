@@ -0,0 +1,127 @@
+use category::prelude::*;
+use category::{
+    axioms, prove, Bindings, CategoryBuilder, ProofBudget, ProofOutcome, SearchStrategy,
+};
+
+#[test]
+fn test_prove_product() {
+    let mut fact = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .build();
+    let goal = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .object("AxB", vec![ObjectTag::Product("A", "B")], ())
+        .morphism("p1", "AxB", "A", vec![MorphismTag::ProductP1], ())
+        .build();
+    let bindings = link(&fact, &goal);
+
+    let rules = [
+        axioms::rule_identity::<&str>().unwrap(),
+        axioms::rule_product::<&str>().unwrap(),
+    ];
+    let budget = ProofBudget {
+        max_depth: 3,
+        max_applications: 1000,
+        time_limit: None,
+    };
+
+    for strategy in [SearchStrategy::BreadthFirst, SearchStrategy::Heuristic] {
+        let outcome = prove(
+            &mut fact,
+            &goal,
+            &bindings,
+            &rules,
+            strategy,
+            &budget,
+            |_| (),
+            |_, _| (),
+            |_| (),
+        )
+        .unwrap();
+        let ProofOutcome::Proved(steps) = outcome else {
+            panic!("Expected a proof, found {outcome:?}");
+        };
+        assert_eq!(steps.len(), 1);
+        assert_eq!(steps[0].rule, 1);
+
+        // The fact category is left untouched
+        assert_eq!(fact.objects.len(), 2);
+        assert_eq!(fact.morphisms.len(), 0);
+
+        // Replaying the proof reaches the goal
+        let mut replay = fact.clone();
+        for step in steps {
+            replay
+                .apply_rule(&rules[step.rule], step.bindings, |_| (), |_, _| (), |_| ())
+                .unwrap();
+        }
+        assert!(replay
+            .find_candidates(&goal.to_constraints(), &bindings)
+            .unwrap()
+            .unwrap()
+            .next()
+            .is_some());
+    }
+}
+
+#[test]
+fn test_prove_budget() {
+    let mut fact = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .build();
+    let goal = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .morphism("f", "A", "B", vec![], ())
+        .build();
+    let bindings = link(&fact, &goal);
+    let rules = [axioms::rule_identity::<&str>().unwrap()];
+
+    // Identities never connect different objects
+    let mut budget = ProofBudget {
+        max_depth: 5,
+        max_applications: 1000,
+        time_limit: None,
+    };
+    let outcome = prove(
+        &mut fact,
+        &goal,
+        &bindings,
+        &rules,
+        SearchStrategy::BreadthFirst,
+        &budget,
+        |_| (),
+        |_, _| (),
+        |_| (),
+    )
+    .unwrap();
+    assert!(matches!(outcome, ProofOutcome::NotFound));
+
+    budget.max_applications = 1;
+    let outcome = prove(
+        &mut fact,
+        &goal,
+        &bindings,
+        &rules,
+        SearchStrategy::BreadthFirst,
+        &budget,
+        |_| (),
+        |_, _| (),
+        |_| (),
+    )
+    .unwrap();
+    assert!(matches!(outcome, ProofOutcome::OutOfBudget));
+}
+
+/// Binds the goal objects to the fact objects with the same ids.
+fn link(fact: &Category<(), (), ()>, goal: &Category<(), (), ()>) -> Bindings<CategoryThing> {
+    Bindings::from_objects(
+        fact.objects
+            .iter()
+            .filter(|(id, _)| goal.objects.get(id).is_some())
+            .map(|(&id, _)| (CategoryThing::Object { id }, id)),
+    )
+}
@@ -0,0 +1,81 @@
+use category::constraint::ConstraintsBuilder;
+use category::prelude::*;
+use category::{axioms, Bindings, CategoryBuilder};
+
+#[test]
+fn test_pullback() {
+    let mut category = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .object("C", vec![], ())
+        .morphism("f", "A", "C", vec![], ())
+        .morphism("g", "B", "C", vec![], ())
+        .build();
+    let bindings = bind_pair(&category);
+
+    let rule_pullback = axioms::rule_pullback::<&str>().unwrap();
+    let result = category
+        .apply_rule(&rule_pullback, bindings.clone(), |_| (), |_, _| (), |_| ())
+        .unwrap();
+    assert!(result.1);
+    assert_eq!(4, category.objects.len());
+    assert_eq!(5, category.morphisms.len());
+    assert_eq!(1, category.equalities.len());
+
+    let constraints = ConstraintsBuilder::new()
+        .object("P", vec![ObjectTag::Pullback("f", "g")])
+        .morphism("p1", "P", "A", vec![MorphismTag::PullbackP1])
+        .morphism("p2", "P", "B", vec![MorphismTag::PullbackP2])
+        .equality(vec!["p1", "f"], vec!["p2", "g"])
+        .build();
+    let candidates = category
+        .find_candidates(&constraints, &bindings)
+        .unwrap()
+        .unwrap()
+        .count();
+    assert_eq!(1, candidates);
+}
+
+#[test]
+fn test_pushout() {
+    let mut category = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .object("C", vec![], ())
+        .morphism("f", "C", "A", vec![], ())
+        .morphism("g", "C", "B", vec![], ())
+        .build();
+    let bindings = bind_pair(&category);
+
+    let rule_pushout = axioms::rule_pushout::<&str>().unwrap();
+    let result = category
+        .apply_rule(&rule_pushout, bindings.clone(), |_| (), |_, _| (), |_| ())
+        .unwrap();
+    assert!(result.1);
+    assert_eq!(4, category.objects.len());
+    assert_eq!(5, category.morphisms.len());
+    assert_eq!(1, category.equalities.len());
+
+    let constraints = ConstraintsBuilder::new()
+        .object("Q", vec![ObjectTag::Pushout("f", "g")])
+        .morphism("j1", "A", "Q", vec![MorphismTag::PushoutJ1])
+        .morphism("j2", "B", "Q", vec![MorphismTag::PushoutJ2])
+        .equality(vec!["f", "j1"], vec!["g", "j2"])
+        .build();
+    let candidates = category
+        .find_candidates(&constraints, &bindings)
+        .unwrap()
+        .unwrap()
+        .count();
+    assert_eq!(1, candidates);
+}
+
+fn bind_pair(category: &Category<(), (), ()>) -> Bindings<&'static str> {
+    let mut morphisms = category
+        .morphisms
+        .iter()
+        .map(|(&id, _)| id)
+        .collect::<Vec<_>>();
+    morphisms.sort();
+    Bindings::from_morphisms(vec![("f", morphisms[0]), ("g", morphisms[1])])
+}
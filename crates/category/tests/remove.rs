@@ -0,0 +1,99 @@
+use category::prelude::*;
+use category::{format, Action, CategoryBuilder};
+
+mod common;
+use common::ids;
+
+#[test]
+fn test_remove_object_cascade() {
+    let mut category = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .object("C", vec![], ())
+        .object(
+            "P",
+            vec![ObjectTag::Product("A", "B"), ObjectTag::Terminal],
+            (),
+        )
+        .morphism("id", "A", "A", vec![MorphismTag::Identity("A")], ())
+        .morphism("f", "A", "B", vec![], ())
+        .morphism("g", "B", "C", vec![], ())
+        .morphism(
+            "h",
+            "A",
+            "C",
+            vec![
+                MorphismTag::Composition {
+                    first: "f",
+                    second: "g",
+                },
+                MorphismTag::Unique,
+            ],
+            (),
+        )
+        .equality(["f", "g"], ["h"], ())
+        .build();
+    assert_eq!(category.validate(), Ok(()));
+    let [a, b, _, p] = ids(category.objects.iter().map(|(&id, _)| id));
+    let [id, _, _, h] = ids(category.morphisms.iter().map(|(&id, _)| id));
+    let before = format::to_string(&category).unwrap();
+
+    let undo = category.action_do(Action::RemoveObjects(vec![b]));
+    assert!(!category.objects.contains(&b));
+    assert_eq!(ids(category.morphisms.iter().map(|(&id, _)| id)), [id, h]);
+    assert_eq!(category.equalities.len(), 0);
+    assert_eq!(category.validate(), Ok(()));
+    assert_eq!(
+        category.objects.get(&p).unwrap().tags,
+        vec![ObjectTag::Terminal]
+    );
+    assert!(category.objects.get(&a).unwrap().tags.is_empty());
+    assert_eq!(
+        category.morphisms.get(&h).unwrap().tags,
+        vec![MorphismTag::Unique]
+    );
+
+    // The inverse restores everything, including the order of the tags
+    let redo: Vec<_> = undo
+        .into_iter()
+        .flat_map(|action| category.action_do(action))
+        .collect();
+    assert_eq!(format::to_string(&category).unwrap(), before);
+
+    for action in redo {
+        category.action_do(action);
+    }
+    assert!(!category.objects.contains(&b));
+    assert_eq!(category.equalities.len(), 0);
+}
+
+#[test]
+fn test_remove_morphism_tags() {
+    let mut category = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .object("C", vec![], ())
+        .morphism("f", "A", "B", vec![], ())
+        .morphism("g", "B", "C", vec![], ())
+        .morphism(
+            "g o f",
+            "A",
+            "C",
+            vec![MorphismTag::Composition {
+                first: "f",
+                second: "g",
+            }],
+            (),
+        )
+        .build();
+    let [f, _, gf] = ids(category.morphisms.iter().map(|(&id, _)| id));
+    let before = format::to_string(&category).unwrap();
+
+    let undo = category.action_do(Action::RemoveMorphisms(vec![f]));
+    assert!(category.morphisms.get(&gf).unwrap().tags.is_empty());
+
+    for action in undo {
+        category.action_do(action);
+    }
+    assert_eq!(format::to_string(&category).unwrap(), before);
+}
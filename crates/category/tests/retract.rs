@@ -0,0 +1,129 @@
+use category::constraint::ConstraintsBuilder;
+use category::prelude::*;
+use category::{axioms, Bindings, CategoryBuilder, RuleBuilder};
+
+mod common;
+use common::ids;
+
+#[test]
+fn test_retract_composition() {
+    let mut category = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .object("C", vec![], ())
+        .morphism("f", "A", "B", vec![], ())
+        .morphism("g", "B", "C", vec![], ())
+        .build();
+    let [f, g] = ids(category.morphisms.iter().map(|(&id, _)| id));
+
+    let rule = axioms::rule_composition::<&str>().unwrap();
+    category
+        .apply_rule(
+            &rule,
+            Bindings::from_morphisms([("f", f), ("g", g)]),
+            |_| (),
+            |_, _| (),
+            |_| (),
+        )
+        .unwrap();
+    assert_eq!(category.morphisms.len(), 3);
+
+    let undo = category.retract([CategoryThing::Morphism { id: f }]);
+    assert_eq!(category.objects.len(), 3);
+    assert_eq!(
+        category
+            .morphisms
+            .iter()
+            .map(|(&id, _)| id)
+            .collect::<Vec<_>>(),
+        vec![g]
+    );
+
+    for action in undo {
+        category.action_do(action);
+    }
+    assert_eq!(category.morphisms.len(), 3);
+    assert!(category.morphisms.contains(&f));
+}
+
+#[test]
+fn test_retract_product() {
+    let mut category = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .build();
+    let [a, b] = ids(category.objects.iter().map(|(&id, _)| id));
+
+    let rule = axioms::rule_product::<&str>().unwrap();
+    category
+        .apply_rule(
+            &rule,
+            Bindings::from_objects([("A", a), ("B", b)]),
+            |_| (),
+            |_, _| (),
+            |_| (),
+        )
+        .unwrap();
+    assert_eq!(category.objects.len(), 3);
+
+    let retraction = category.consequences([CategoryThing::Object { id: a }]);
+    assert_eq!(retraction.objects.len(), 2);
+    assert_eq!(retraction.morphisms.len(), 3);
+
+    category.retract([CategoryThing::Object { id: a }]);
+    assert_eq!(
+        category
+            .objects
+            .iter()
+            .map(|(&id, _)| id)
+            .collect::<Vec<_>>(),
+        vec![b]
+    );
+    assert_eq!(category.morphisms.len(), 0);
+}
+
+#[test]
+fn test_retract_derived_equality() {
+    let mut category = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .morphism("f", "A", "B", vec![], ())
+        .morphism("g", "A", "B", vec![], ())
+        .morphism("h", "A", "B", vec![], ())
+        .equality(["f"], ["g"], ())
+        .build();
+    let [f, g, h] = ids(category.morphisms.iter().map(|(&id, _)| id));
+
+    let rule = RuleBuilder::<&str>::new()
+        .forall(
+            ConstraintsBuilder::new()
+                .morphism("f", "A", "B", vec![])
+                .morphism("g", "A", "B", vec![])
+                .morphism("h", "A", "B", vec![])
+                .equality(["f"], ["g"]),
+        )
+        .exists(ConstraintsBuilder::new().equality(["f"], ["h"]))
+        .build()
+        .unwrap();
+    category
+        .apply_rule(
+            &rule,
+            Bindings::from_morphisms([("f", f), ("g", g), ("h", h)]),
+            |_| (),
+            |_, _| (),
+            |_| (),
+        )
+        .unwrap();
+    assert_eq!(category.equalities.len(), 2);
+
+    // The equality between `f` and `h` has been derived using `g`
+    let undo = category.retract([CategoryThing::Morphism { id: g }]);
+    assert_eq!(category.morphisms.len(), 2);
+    assert_eq!(category.equalities.len(), 0);
+
+    for action in undo {
+        category.action_do(action);
+    }
+    assert_eq!(category.morphisms.len(), 3);
+    assert_eq!(category.equalities.len(), 2);
+}
@@ -0,0 +1,119 @@
+use category::prelude::*;
+use category::{axioms, util, CategoryBuilder, Rule, SaturationBudget};
+
+#[test]
+fn test_saturate_fixpoint() {
+    let mut category = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .morphism("f", "A", "B", vec![], ())
+        .build();
+    let rules = [axioms::rule_identity::<&str>().unwrap()];
+    let budget = SaturationBudget {
+        max_rounds: 10,
+        max_objects: 10,
+        max_path_length: 3,
+        max_product_depth: 1,
+    };
+
+    let saturation = category
+        .saturate(&rules, &budget, |_| (), |_, _| (), |_| ())
+        .unwrap();
+    assert!(saturation.fixpoint);
+    assert_eq!(saturation.rounds.len(), 1);
+    assert_eq!(saturation.rounds[0].morphisms.len(), 2);
+    assert_eq!(category.morphisms.len(), 3);
+
+    for action in saturation.undo {
+        category.action_do(action);
+    }
+    assert_eq!(category.morphisms.len(), 1);
+}
+
+#[test]
+fn test_saturate_budget() {
+    let mut category = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .object("C", vec![], ())
+        .morphism("f", "A", "B", vec![], ())
+        .morphism("g", "B", "C", vec![], ())
+        .build();
+    let rules = [
+        axioms::rule_identity::<&str>().unwrap(),
+        axioms::rule_composition::<&str>().unwrap(),
+    ];
+    let budget = SaturationBudget {
+        max_rounds: 10,
+        max_objects: 10,
+        max_path_length: 2,
+        max_product_depth: 1,
+    };
+
+    // Composing with identities never ends without a budget
+    let saturation = category
+        .saturate(&rules, &budget, |_| (), |_, _| (), |_| ())
+        .unwrap();
+    assert!(!saturation.fixpoint);
+    assert!(saturation.rounds.iter().any(|round| round.rejected > 0));
+    assert!(category
+        .morphisms
+        .iter()
+        .all(|(&id, _)| util::decompose_morphism(id, &category).len() <= 2));
+    let added: usize = saturation
+        .rounds
+        .iter()
+        .map(|round| round.morphisms.len())
+        .sum();
+    assert_eq!(category.morphisms.len(), 2 + added);
+}
+
+#[test]
+fn test_saturate_products() {
+    let mut category = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .build();
+    let rules = [axioms::rule_product::<&str>().unwrap()];
+    let budget = SaturationBudget {
+        max_rounds: 10,
+        max_objects: 10,
+        max_path_length: 1,
+        max_product_depth: 1,
+    };
+
+    let saturation = category
+        .saturate(&rules, &budget, |_| (), |_, _| (), |_| ())
+        .unwrap();
+    assert!(!saturation.fixpoint);
+    // A x A, A x B, B x A, B x B
+    assert_eq!(saturation.rounds[0].objects.len(), 4);
+    assert_eq!(category.objects.len(), 6);
+}
+
+#[test]
+fn test_saturate_tags() {
+    let mut category = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .build();
+    let rules = [Rule::<&str>::parse("forall A. exists A [terminal].").unwrap()];
+    let budget = SaturationBudget {
+        max_rounds: 10,
+        max_objects: 10,
+        max_path_length: 1,
+        max_product_depth: 1,
+    };
+
+    // A round that only adds tags still changes the category
+    let saturation = category
+        .saturate(&rules, &budget, |_| (), |_, _| (), |_| ())
+        .unwrap();
+    assert!(saturation.fixpoint);
+    assert_eq!(saturation.rounds.len(), 1);
+    assert_eq!(saturation.rounds[0].object_tags.len(), 2);
+    assert!(category
+        .objects
+        .iter()
+        .all(|(_, object)| object.tags == vec![ObjectTag::Terminal]));
+}
@@ -0,0 +1,319 @@
+use category::prelude::*;
+use category::{
+    axioms, Bindings, CategoryBuilder, ProofScript, Rule, RuleId, ScriptError, ScriptRecorder,
+    ScriptRef,
+};
+
+mod common;
+use common::ids;
+
+#[test]
+fn test_script_replay() {
+    let rules = [
+        axioms::rule_identity::<&str>().unwrap(),
+        axioms::rule_product::<&str>().unwrap(),
+        axioms::rule_composition::<&str>().unwrap(),
+    ];
+    let mut category = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .build();
+    let [a, b] = ids(category.objects.iter().map(|(&id, _)| id));
+
+    // Record the steps
+    let mut recorder = ScriptRecorder::new(&Bindings::from_objects([("A", a), ("B", b)]));
+    let mut apply = |category: &mut Category<(), (), ()>, rule: usize, bindings: Bindings<_>| {
+        let (_, applied, step) = recorder
+            .apply_rule(category, &rules[rule], bindings, |_| (), |_, _| (), |_| ())
+            .unwrap();
+        assert!(applied);
+        recorder.push(step);
+    };
+    apply(
+        &mut category,
+        1,
+        Bindings::from_objects([("A", a), ("B", b)]),
+    );
+    let p1 = find_morphism(&category, |tag| matches!(tag, MorphismTag::ProductP1));
+    apply(&mut category, 0, Bindings::single_object("A", a));
+    let id_a = find_morphism(
+        &category,
+        |tag| matches!(tag, MorphismTag::Identity(id) if *id == a),
+    );
+    apply(
+        &mut category,
+        2,
+        Bindings::from_morphisms([("f", p1), ("g", id_a)]),
+    );
+
+    let script = recorder.script().clone();
+    assert_eq!(script.steps.len(), 3);
+    assert_eq!(script.steps[0].rule, RuleId::new("product"));
+    assert_eq!(
+        script.steps[0].objects,
+        vec![
+            ("A", ScriptRef::Initial("A")),
+            ("B", ScriptRef::Initial("B"))
+        ]
+    );
+    assert!(matches!(
+        &script.steps[2].morphisms[..],
+        [
+            (
+                "f",
+                ScriptRef::Created {
+                    step: 0,
+                    label: "p1",
+                    ..
+                }
+            ),
+            (
+                "g",
+                ScriptRef::Created {
+                    step: 1,
+                    label: "id",
+                    ..
+                }
+            ),
+        ]
+    ));
+
+    // The script survives serialization
+    let json = serde_json::to_string(&script).unwrap();
+    let script: ProofScript<String> = serde_json::from_str(&json).unwrap();
+    // The rules are looked up by their ids, not by their order
+    let rules = axioms::rule_axioms::<String>();
+
+    // Replay on a fresh category, where the ids are different
+    let mut fresh = CategoryBuilder::<(), (), (), &str>::new()
+        .object("C", vec![], ())
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .build();
+    let [c, fresh_a, fresh_b] = ids(fresh.objects.iter().map(|(&id, _)| id));
+    fresh.remove_object(c);
+    let initial = Bindings::from_objects([("A".to_owned(), fresh_a), ("B".to_owned(), fresh_b)]);
+
+    script
+        .verify(&mut fresh, &initial, &rules, |_| (), |_, _| (), |_| ())
+        .unwrap();
+    assert_eq!(fresh.objects.len(), 2);
+    assert_eq!(fresh.morphisms.len(), 0);
+
+    script
+        .replay(&mut fresh, &initial, &rules, |_| (), |_, _| (), |_| ())
+        .unwrap();
+    assert_eq!(fresh.objects.len(), category.objects.len());
+    assert_eq!(fresh.morphisms.len(), category.morphisms.len());
+    assert_eq!(fresh.equalities.len(), category.equalities.len());
+
+    // A step that does not match its rule
+    let mut invalid = script.clone();
+    invalid.steps[2].rule = RuleId::new("product");
+    let mut fresh = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .build();
+    let [a, b] = ids(fresh.objects.iter().map(|(&id, _)| id));
+    let initial = Bindings::from_objects([("A".to_owned(), a), ("B".to_owned(), b)]);
+    assert_eq!(
+        invalid.verify(&mut fresh, &initial, &rules, |_| (), |_, _| (), |_| ()),
+        Err(ScriptError::NotAnInstance { step: 2 })
+    );
+    assert_eq!(fresh.objects.len(), 2);
+    assert_eq!(fresh.morphisms.len(), 0);
+
+    // A rule that is not in the list
+    let mut unknown = script.clone();
+    unknown.steps[0].rule = RuleId::new("lemma");
+    assert_eq!(
+        unknown.verify(&mut fresh, &initial, &rules, |_| (), |_, _| (), |_| ()),
+        Err(ScriptError::UnknownRule { step: 0 })
+    );
+
+    // An initial object that is not named
+    let initial = Bindings::single_object("A".to_owned(), a);
+    assert_eq!(
+        script.verify(&mut fresh, &initial, &rules, |_| (), |_, _| (), |_| ()),
+        Err(ScriptError::UnresolvedReference {
+            step: 0,
+            label: "B".to_owned()
+        })
+    );
+}
+
+#[test]
+fn test_script_replay_cones() {
+    // Three cones over A and B, each getting its own mediating morphism
+    let rules = axioms::rule_axioms::<&str>();
+    let product = axioms::rule_product::<&str>().unwrap();
+    let composition = axioms::rule_composition::<&str>().unwrap();
+    let (mut category, initial) = cones();
+    let [a, b, _, c2, _] = ids(category.objects.iter().map(|(&id, _)| id));
+
+    let mut recorder = ScriptRecorder::new(&initial);
+    let (_, _, step) = recorder
+        .apply_rule(
+            &mut category,
+            &product,
+            Bindings::from_objects([("A", a), ("B", b)]),
+            |_| (),
+            |_, _| (),
+            |_| (),
+        )
+        .unwrap();
+    recorder.push(step);
+    let product_object = category
+        .objects
+        .iter()
+        .find(|(_, object)| object.tags.contains(&ObjectTag::Product(a, b)))
+        .map(|(&id, _)| id)
+        .unwrap();
+    let p1 = find_morphism(&category, |tag| matches!(tag, MorphismTag::ProductP1));
+    let mediating = find_connection(&category, c2, product_object);
+
+    // Compose the mediating morphism of the second cone with the projection
+    let (_, _, step) = recorder
+        .apply_rule(
+            &mut category,
+            &composition,
+            Bindings::from_morphisms([("f", mediating), ("g", p1)]),
+            |_| (),
+            |_, _| (),
+            |_| (),
+        )
+        .unwrap();
+    recorder.push(step);
+    assert_composite(&category, c2, a);
+
+    let script = recorder.script();
+    for _ in 0..20 {
+        let (mut fresh, initial) = cones();
+        let [a, _, _, c2, _] = ids(fresh.objects.iter().map(|(&id, _)| id));
+        script
+            .replay(&mut fresh, &initial, &rules, |_| (), |_, _| (), |_| ())
+            .unwrap();
+        assert_eq!(fresh.objects.len(), category.objects.len());
+        assert_eq!(fresh.morphisms.len(), category.morphisms.len());
+        assert_eq!(fresh.equalities.len(), category.equalities.len());
+
+        // The composite starts at the same cone
+        assert_composite(&fresh, c2, a);
+    }
+}
+
+#[test]
+fn test_script_push_pop() {
+    let rule = axioms::rule_identity::<&str>().unwrap();
+    let mut category = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .build();
+    let [a, b] = ids(category.objects.iter().map(|(&id, _)| id));
+
+    let mut recorder = ScriptRecorder::new(&Bindings::from_objects([("A", a), ("B", b)]));
+    for object in [a, b] {
+        let bindings = Bindings::single_object("A", object);
+        let (_, _, step) = recorder
+            .apply_rule(&mut category, &rule, bindings, |_| (), |_, _| (), |_| ())
+            .unwrap();
+        recorder.push(step);
+    }
+
+    let step = recorder.pop().unwrap();
+    assert_eq!(recorder.script().steps.len(), 1);
+    recorder.push(step);
+    assert_eq!(recorder.script().steps.len(), 2);
+    assert_eq!(
+        recorder.script().steps[1].objects,
+        vec![("A", ScriptRef::Initial("B"))]
+    );
+
+    // Nothing is applied when the application cannot be recorded
+    let morphisms = category.morphisms.len();
+    let mut recorder = ScriptRecorder::new(&Bindings::new());
+    let bindings = Bindings::single_object("A", a);
+    let error = recorder
+        .apply_rule(&mut category, &rule, bindings, |_| (), |_, _| (), |_| ())
+        .unwrap_err();
+    assert_eq!(
+        error,
+        ScriptError::UnresolvedReference {
+            step: 0,
+            label: "A"
+        }
+    );
+
+    let unnamed = Rule::<&str>::parse("forall A. exists B [terminal].").unwrap();
+    let error = recorder
+        .apply_rule(
+            &mut category,
+            &unnamed,
+            Bindings::new(),
+            |_| (),
+            |_, _| (),
+            |_| (),
+        )
+        .unwrap_err();
+    assert_eq!(error, ScriptError::UnnamedRule { step: 0 });
+    assert_eq!(category.morphisms.len(), morphisms);
+    assert_eq!(category.objects.len(), 2);
+}
+
+/// Builds the objects `A`, `B`, and the cones `fi: Ci -> A`, `gi: Ci -> B`,
+/// with every thing bound to its name.
+fn cones() -> (Category<(), (), ()>, Bindings<&'static str>) {
+    let mut builder = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ());
+    for (c, f, g) in [("C1", "f1", "g1"), ("C2", "f2", "g2"), ("C3", "f3", "g3")] {
+        builder = builder
+            .object(c, vec![], ())
+            .morphism(f, c, "A", vec![], ())
+            .morphism(g, c, "B", vec![], ());
+    }
+    let category = builder.build();
+
+    let [a, b, c1, c2, c3] = ids(category.objects.iter().map(|(&id, _)| id));
+    let [f1, g1, f2, g2, f3, g3] = ids(category.morphisms.iter().map(|(&id, _)| id));
+    let mut initial =
+        Bindings::from_objects([("A", a), ("B", b), ("C1", c1), ("C2", c2), ("C3", c3)]);
+    initial.extend(Bindings::from_morphisms([
+        ("f1", f1),
+        ("g1", g1),
+        ("f2", f2),
+        ("g2", g2),
+        ("f3", f3),
+        ("g3", g3),
+    ]));
+    (category, initial)
+}
+
+/// Checks that the composite morphism leads from one object to the other.
+fn assert_composite(category: &Category<(), (), ()>, from: ObjectId, to: ObjectId) {
+    let composite = find_morphism(category, |tag| {
+        matches!(tag, MorphismTag::Composition { .. })
+    });
+    assert_eq!(
+        category.morphisms.get(&composite).unwrap().connection,
+        MorphismConnection::Regular { from, to }
+    );
+}
+
+/// Returns the only morphism from one object to the other.
+fn find_connection(category: &Category<(), (), ()>, from: ObjectId, to: ObjectId) -> MorphismId {
+    let [morphism] = ids(category.morphisms.between(from, to));
+    morphism
+}
+
+fn find_morphism(
+    category: &Category<(), (), ()>,
+    tag: impl Fn(&MorphismTag) -> bool,
+) -> MorphismId {
+    category
+        .morphisms
+        .iter()
+        .find(|(_, morphism)| morphism.tags.iter().any(&tag))
+        .map(|(&id, _)| id)
+        .expect("The morphism is expected to exist")
+}
@@ -0,0 +1,81 @@
+use category::prelude::*;
+use category::{CategoryBuilder, PathEquality, DEFAULT_SOLVER_BUDGET};
+
+mod common;
+use common::ids;
+
+#[test]
+fn test_solver_expansion() {
+    //   x     y     w
+    // A --> B --> C --> D
+    // z = y ∘ x: A -> C
+    // v = w ∘ y: B -> D
+    let category = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .object("C", vec![], ())
+        .object("D", vec![], ())
+        .morphism("id", "A", "A", vec![MorphismTag::Identity("A")], ())
+        .morphism("x", "A", "B", vec![], ())
+        .morphism("y", "B", "C", vec![], ())
+        .morphism("w", "C", "D", vec![], ())
+        .morphism("z", "A", "C", vec![], ())
+        .morphism("v", "B", "D", vec![], ())
+        .equality(vec!["x", "y"], vec!["z"], ())
+        .equality(vec!["y", "w"], vec!["v"], ())
+        .build();
+    let [id, x, y, w, z, v] = ids(category.morphisms.iter().map(|(&id, _)| id));
+
+    // w ∘ z = w ∘ y ∘ x = v ∘ x, which requires expanding z into a longer path
+    assert_eq!(
+        PathEquality::Equal,
+        category.solve_paths(&[z, w], &[x, v], DEFAULT_SOLVER_BUDGET)
+    );
+    assert_eq!(
+        PathEquality::Equal,
+        category.solve_paths(&[id, x, y], &[z], DEFAULT_SOLVER_BUDGET)
+    );
+
+    // Different endpoints
+    assert_eq!(
+        PathEquality::NotEqual,
+        category.solve_paths(&[x, y], &[x, y, w], DEFAULT_SOLVER_BUDGET)
+    );
+    let solver = category.path_solver(DEFAULT_SOLVER_BUDGET);
+    assert!(solver.is_complete());
+    assert_eq!(solver.normal_form(&[x, y, w]), solver.normal_form(&[z, w]));
+
+    // Not enough budget to derive the rules
+    assert_eq!(
+        PathEquality::Unknown,
+        category.solve_paths(&[z, w], &[x, v], 0)
+    );
+}
+
+#[test]
+fn test_solver_infinite() {
+    // An endomorphism e with e ∘ e = e does not make e an identity
+    let category = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .morphism("e", "A", "A", vec![], ())
+        .morphism("f", "A", "B", vec![], ())
+        .morphism("g", "A", "B", vec![], ())
+        .equality(vec!["e", "e"], vec!["e"], ())
+        .equality(vec!["e", "f"], vec!["e", "g"], ())
+        .build();
+    let [e, f, g] = ids(category.morphisms.iter().map(|(&id, _)| id));
+
+    assert_eq!(
+        PathEquality::Equal,
+        category.solve_paths(&[e, e, e, f], &[e, g], DEFAULT_SOLVER_BUDGET)
+    );
+    assert_eq!(
+        PathEquality::NotEqual,
+        category.solve_paths(&[f], &[g], DEFAULT_SOLVER_BUDGET)
+    );
+    assert_eq!(
+        PathEquality::NotEqual,
+        category.solve_paths(&[e, f], &[f], DEFAULT_SOLVER_BUDGET)
+    );
+}
@@ -35,7 +35,9 @@ fn test_substitution() {
     let rule_unique = axioms::rule_unique::<&str>().unwrap();
 
     // Apply unique rule
-    category.apply_rule(&rule_unique, Bindings::new(), |_| (), |_, _| (), |_| ());
+    category
+        .apply_rule(&rule_unique, Bindings::new(), |_| (), |_, _| (), |_| ())
+        .unwrap();
     print_category(&category);
     assert_eq!(2, category.objects.len());
     assert_eq!(4, category.morphisms.len());
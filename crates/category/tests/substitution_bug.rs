@@ -25,7 +25,9 @@ fn test_bug() {
     assert_eq!(1, category.equalities.len());
 
     // Substitute g=m
-    let result = category.apply_rule(&rule_unique, Bindings::new(), |_| (), |_, _| (), |_| ());
+    let result = category
+        .apply_rule(&rule_unique, Bindings::new(), |_| (), |_, _| (), |_| ())
+        .unwrap();
     assert!(result.1);
 
     print_category(&category);
@@ -0,0 +1,117 @@
+use category::text::parse_statement;
+use category::{axioms, Constraint, Rule, RuleConstruction};
+
+#[test]
+fn test_axioms_text() {
+    for rule in axioms::rule_axioms::<String>() {
+        let text = rule.to_text();
+        println!("{text}\n");
+        let parsed = Rule::<String>::parse(&text).unwrap();
+        assert_eq!(rule.get_statement(), parsed.get_statement());
+        assert_eq!(text, parsed.to_text());
+    }
+}
+
+#[test]
+fn test_parse_rule() {
+    let rule = Rule::<&str>::parse(
+        "forall f: A -> B, g: B -> C.
+         exists \"g o f\": A -> C [composition(f, g)]",
+    )
+    .unwrap();
+    assert_eq!(
+        axioms::rule_composition::<&str>().unwrap().get_statement(),
+        rule.get_statement()
+    );
+
+    let rule = Rule::<&str>::parse(
+        "forall A, B.
+         exists AxB [product(A, B)].
+         exists p1: AxB -> A [product_p1], p2: AxB -> B [product_p2].
+         forall C, f: C -> A, g: C -> B.
+         exists m: C -> AxB, p1 ∘ m = f, p2 ∘ m = g.",
+    )
+    .unwrap();
+    assert_eq!(5, rule.get_statement().len());
+}
+
+#[test]
+fn test_parse_errors() {
+    let text = "forall f: A B";
+    let error = parse_statement::<&str>(text).unwrap_err();
+    assert_eq!(12..13, error.span);
+    assert_eq!(
+        "error at 1:13: expected `->` or `<->`, found label `B`\nforall f: A B\n            ^",
+        error.report(text)
+    );
+
+    let text = "forall A.\nexists B [prodcut(A, A)]";
+    let error = parse_statement::<&str>(text).unwrap_err();
+    assert_eq!(20..27, error.span);
+    assert!(error.message.starts_with("unknown tag `prodcut`"));
+
+    let text = "forall A [product(A)]";
+    let error = parse_statement::<&str>(text).unwrap_err();
+    assert_eq!(10..20, error.span);
+    assert_eq!(
+        "tag `product` expects 2 argument(s), found 1",
+        error.message
+    );
+
+    let text = "exists \"A";
+    let error = parse_statement::<&str>(text).unwrap_err();
+    assert_eq!(7..9, error.span);
+}
+
+#[test]
+fn test_composed_label() {
+    let text = "forall f: A -> B, g: B -> C.\nexists g∘f: A -> C [composition(f, g)].";
+    let rule = Rule::<&str>::parse(text).unwrap();
+    let Some(RuleConstruction::Exists(constraints)) = rule.get_statement().last() else {
+        panic!("Expected an exists construction");
+    };
+    assert!(matches!(
+        constraints[..],
+        [Constraint::Morphism { label: "g∘f", .. }]
+    ));
+    assert_eq!(text, rule.to_text());
+
+    // A composition must either be a label or be equal to something
+    let text = "forall f: A -> B, g: B -> C.\nexists g∘f [terminal].";
+    let error = parse_statement::<&str>(text).unwrap_err();
+    assert_eq!("expected `=` or `:`, found `[`", error.message);
+}
+
+#[test]
+fn test_quoted_labels() {
+    let text = r###"forall #"say "hi""#, ##"a"#"##.
+exists #"say "hi""# [product(#"say "hi""#, ##"a"#"##)]."###;
+    let rule = Rule::<&str>::parse(text).unwrap();
+    let Some(RuleConstruction::Forall(constraints)) = rule.get_statement().first() else {
+        panic!("Expected a forall construction");
+    };
+    assert!(matches!(
+        constraints[..],
+        [
+            Constraint::Object {
+                label: "say \"hi\"",
+                ..
+            },
+            Constraint::Object { label: "a\"#", .. }
+        ]
+    ));
+    assert_eq!(text, rule.to_text());
+}
+
+#[test]
+fn test_invalid_rule_span() {
+    let text = "forall f: A -> B, g: A -> B.\nexists h: B -> A, g ∘ f = h.";
+    let error = Rule::<&str>::parse(text).unwrap_err();
+    let start = text.find("g ∘ f").unwrap();
+    assert_eq!(start..start + "g ∘ f = h".len(), error.span);
+    assert!(error.message.starts_with("invalid rule: NotComposable"));
+
+    let text = "forall A.\nforall B.";
+    let error = Rule::<&str>::parse(text).unwrap_err();
+    assert_eq!(10..18, error.span);
+}
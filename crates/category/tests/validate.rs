@@ -0,0 +1,112 @@
+use category::prelude::*;
+use category::{axioms, Bindings, CategoryBuilder, Equality, InvariantViolation};
+
+mod common;
+use common::ids;
+
+#[test]
+fn test_validate_valid() {
+    let mut category = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .build();
+    let [a, b] = ids(category.objects.iter().map(|(&id, _)| id));
+    let rule = axioms::rule_product::<&str>().unwrap();
+    category
+        .apply_rule(
+            &rule,
+            Bindings::from_objects([("A", a), ("B", b)]),
+            |_| (),
+            |_, _| (),
+            |_| (),
+        )
+        .unwrap();
+    assert_eq!(category.validate(), Ok(()));
+}
+
+#[test]
+fn test_validate_tags() {
+    let category = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .object("AxB", vec![ObjectTag::Product("A", "B")], ())
+        .morphism("id", "A", "B", vec![MorphismTag::Identity("A")], ())
+        .morphism("f", "A", "B", vec![], ())
+        .morphism("g", "A", "B", vec![], ())
+        .morphism(
+            "g o f",
+            "A",
+            "B",
+            vec![MorphismTag::Composition {
+                first: "f",
+                second: "g",
+            }],
+            (),
+        )
+        .morphism("p1", "AxB", "B", vec![MorphismTag::ProductP1], ())
+        .morphism("p2", "AxB", "B", vec![MorphismTag::ProductP2], ())
+        .build();
+    let [a, _, _] = ids(category.objects.iter().map(|(&id, _)| id));
+    let [id, f, g, gf, p1, _] = ids(category.morphisms.iter().map(|(&id, _)| id));
+
+    assert_eq!(
+        category.validate(),
+        Err(vec![
+            InvariantViolation::IdentityNotLoop {
+                morphism: id,
+                object: a,
+            },
+            InvariantViolation::BadComposition {
+                morphism: gf,
+                first: f,
+                second: g,
+            },
+            InvariantViolation::BadProjection { morphism: p1 },
+        ])
+    );
+}
+
+#[test]
+fn test_validate_equalities() {
+    let category = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .morphism("f", "A", "B", vec![], ())
+        .morphism("g", "A", "B", vec![], ())
+        .morphism("h", "B", "A", vec![], ())
+        .equality(["f"], ["g"], ())
+        .equality(["f", "g"], ["h"], ())
+        .build();
+    let [f, g, h] = ids(category.morphisms.iter().map(|(&id, _)| id));
+
+    assert_eq!(
+        category.validate(),
+        Err(vec![InvariantViolation::NonComposableEquality {
+            equality: Equality::new(vec![f, g], vec![h]).unwrap(),
+        }])
+    );
+}
+
+#[test]
+fn test_validate_dangling() {
+    let mut category = CategoryBuilder::<(), (), (), &str>::new()
+        .object("A", vec![], ())
+        .object("B", vec![], ())
+        .object("AxB", vec![ObjectTag::Product("A", "B")], ())
+        .morphism("f", "A", "B", vec![], ())
+        .equality(["f"], ["f"], ())
+        .build();
+    let [a, b, product] = ids(category.objects.iter().map(|(&id, _)| id));
+    let [f] = ids(category.morphisms.iter().map(|(&id, _)| id));
+
+    // Removing directly from the graph leaves the references behind
+    category.remove_object(a);
+    let violations = category.validate().unwrap_err();
+    assert!(violations.contains(&InvariantViolation::DanglingObjectTag {
+        object: product,
+        tag: ObjectTag::Product(a, b),
+    }));
+    assert!(violations.contains(&InvariantViolation::DanglingEquality {
+        equality: Equality::new(vec![f], vec![f]).unwrap(),
+    }));
+}
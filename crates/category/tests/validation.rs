@@ -0,0 +1,146 @@
+use category::constraint::ConstraintsBuilder;
+use category::prelude::*;
+use category::{Equality, RuleBuilder, RuleConstructionError};
+
+#[test]
+fn test_empty_statement() {
+    let result = RuleBuilder::<&str>::new().build();
+    assert_eq!(
+        Err(RuleConstructionError::EmptyStatement),
+        result.map(|_| ())
+    );
+}
+
+#[test]
+fn test_undefined_label() {
+    // Tag refers to an object that is never introduced
+    let result = RuleBuilder::<&str>::new()
+        .forall(ConstraintsBuilder::new().object("A", vec![]))
+        .exists(ConstraintsBuilder::new().object("AxB", vec![ObjectTag::Product("A", "B")]))
+        .build();
+    assert_eq!(
+        Err(RuleConstructionError::UndefinedLabel {
+            label: "B",
+            construction: 1
+        }),
+        result.map(|_| ())
+    );
+
+    // Object tags in an `exists` may only use labels from earlier constructions
+    let result = RuleBuilder::<&str>::new()
+        .exists(
+            ConstraintsBuilder::new()
+                .object("A", vec![])
+                .object("B", vec![])
+                .object("AxB", vec![ObjectTag::Product("A", "B")]),
+        )
+        .build();
+    assert_eq!(
+        Err(RuleConstructionError::UndefinedLabel {
+            label: "A",
+            construction: 0
+        }),
+        result.map(|_| ())
+    );
+
+    // Equality refers to a morphism introduced later
+    let result = RuleBuilder::<&str>::new()
+        .forall(
+            ConstraintsBuilder::new()
+                .morphism("f", "A", "B", vec![])
+                .equality(["f"], ["g"])
+                .morphism("g", "A", "B", vec![]),
+        )
+        .exists(ConstraintsBuilder::new().morphism("h", "A", "B", vec![]))
+        .build();
+    assert_eq!(
+        Err(RuleConstructionError::UndefinedLabel {
+            label: "g",
+            construction: 0
+        }),
+        result.map(|_| ())
+    );
+}
+
+#[test]
+fn test_conflicting_labels() {
+    let result = RuleBuilder::<&str>::new()
+        .forall(ConstraintsBuilder::new().morphism("f", "A", "B", vec![]))
+        .exists(ConstraintsBuilder::new().morphism("f", "B", "A", vec![]))
+        .build();
+    assert_eq!(
+        Err(RuleConstructionError::ConflictingConnections {
+            label: "f",
+            construction: 1,
+            first: MorphismConnection::Regular { from: "A", to: "B" },
+            second: MorphismConnection::Regular { from: "B", to: "A" },
+        }),
+        result.map(|_| ())
+    );
+
+    let result = RuleBuilder::<&str>::new()
+        .forall(ConstraintsBuilder::new().morphism("f", "A", "B", vec![]))
+        .exists(ConstraintsBuilder::new().object("f", vec![]))
+        .build();
+    assert_eq!(
+        Err(RuleConstructionError::ConflictingKinds {
+            label: "f",
+            construction: 1
+        }),
+        result.map(|_| ())
+    );
+}
+
+#[test]
+fn test_not_composable() {
+    let result = RuleBuilder::<&str>::new()
+        .forall(
+            ConstraintsBuilder::new()
+                .morphism("f", "A", "B", vec![])
+                .morphism("g", "A", "B", vec![]),
+        )
+        .exists(ConstraintsBuilder::new().equality(["f", "g"], ["f"]))
+        .build();
+    assert_eq!(
+        Err(RuleConstructionError::NotComposable {
+            equality: Equality::new(vec!["f", "g"], vec!["f"]).unwrap(),
+            construction: 1
+        }),
+        result.map(|_| ())
+    );
+
+    // Paths compose, but have different endpoints
+    let result = RuleBuilder::<&str>::new()
+        .forall(
+            ConstraintsBuilder::new()
+                .morphism("f", "A", "B", vec![])
+                .morphism("g", "B", "A", vec![]),
+        )
+        .exists(ConstraintsBuilder::new().equality(["f", "g"], ["f"]))
+        .build();
+    assert!(matches!(
+        result,
+        Err(RuleConstructionError::NotComposable { .. })
+    ));
+}
+
+#[test]
+fn test_output_introduces_nothing() {
+    let result = RuleBuilder::<&str>::new()
+        .forall(ConstraintsBuilder::new().morphism("f", "A", "B", vec![]))
+        .exists(ConstraintsBuilder::new().morphism("f", "A", "B", vec![]))
+        .build();
+    assert_eq!(
+        Err(RuleConstructionError::OutputIntroducesNothing),
+        result.map(|_| ())
+    );
+
+    let result = RuleBuilder::<&str>::new()
+        .exists(ConstraintsBuilder::new().object("A", vec![]))
+        .forall(ConstraintsBuilder::new().object("B", vec![]))
+        .build();
+    assert_eq!(
+        Err(RuleConstructionError::OutputIntroducesNothing),
+        result.map(|_| ())
+    );
+}
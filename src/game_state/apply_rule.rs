@@ -8,24 +8,26 @@ impl GameState {
             FocusedCategory::Goal => &mut self.goal_category,
         };
         let rule = &self.rules[selection.rule()];
-        let (rule, rule_input) = match selection.inverse() {
-            Some(inverse) => (&rule.inverse[inverse], &rule.inverse_input),
-            None => (&rule.inner, &rule.input),
+        let rule = match selection.inverse() {
+            Some(inverse) => &rule.inverse[inverse],
+            None => &rule.inner,
         };
 
-        let (mut undo_actions, applied) = category.inner.apply_rule(
-            rule,
-            selection.get_bindings().clone(),
-            |tags| {
-                let label = tags
-                    .into_iter()
-                    .find_map(|tag| {
-                        object_name_from_tag_label(&tag.map(|object| object.inner.label.as_str()))
-                    })
-                    .unwrap_or_default();
-                Point::new(label, Color::WHITE)
-            },
-            |connection, tags| {
+        let object_constructor = |tags: Vec<ObjectTag<&Object, &Morphism>>| {
+            let label = tags
+                .into_iter()
+                .find_map(|tag| {
+                    object_name_from_tag_label(&tag.map(
+                        |object| object.inner.label.as_str(),
+                        |morphism| morphism.inner.label.as_deref().unwrap_or_default(),
+                    ))
+                })
+                .unwrap_or_default();
+            Point::new(label, Color::WHITE)
+        };
+        let morphism_constructor =
+            |connection: MorphismConnection<&Object>,
+             tags: Vec<MorphismTag<&Object, &Morphism>>| {
                 let color = match connection {
                     MorphismConnection::Isomorphism(_, _) => ARROW_ISOMORPHISM_COLOR,
                     MorphismConnection::Regular { .. } => tags
@@ -37,11 +39,28 @@ impl GameState {
                         .unwrap_or(ARROW_REGULAR_COLOR),
                 };
                 Arrow::new::<Label>(None, color, util::random_shift(), util::random_shift())
-            },
-            |_equality| Equality {
-                color: constants::EQUALITY_FONT_COLOR,
-            },
+            };
+        let equality_constructor = |_equality: &category::Equality| Equality {
+            color: constants::EQUALITY_FONT_COLOR,
+        };
+
+        // Only the applications that can be recorded into the script are allowed
+        let bindings = selection.get_bindings().clone();
+        let result = category.script.apply_rule(
+            &mut category.inner,
+            rule,
+            bindings,
+            object_constructor,
+            morphism_constructor,
+            equality_constructor,
         );
+        let (undo_actions, applied, step) = match result {
+            Ok(result) => result,
+            Err(error) => {
+                warn!("Failed to apply the rule: {error:?}");
+                return;
+            }
+        };
 
         for action in &undo_actions {
             match action {
@@ -64,42 +83,70 @@ impl GameState {
         }
 
         if applied {
-            if selection.inverse().is_some() {
-                let bindings = selection.get_bindings();
-                let remove_morphisms = rule_input
-                    .iter()
-                    .filter_map(|input| match input {
-                        RuleInput::Morphism { label, .. } => bindings.get_morphism(label),
-                        _ => None,
-                    })
-                    .filter(|&id| {
-                        // Check that there are no equalities with that morphism
-                        category
-                            .inner
-                            .equalities
-                            .get_equalities_with(id)
-                            .next()
-                            .is_none()
-                    })
-                    .filter_map(|id| {
-                        category
-                            .inner
-                            .morphisms
-                            .remove(&id)
-                            .map(|morphism| (Some(id), morphism))
-                    })
-                    .collect::<Vec<_>>();
-                if !remove_morphisms.is_empty() {
-                    undo_actions.push(CategoryAction::NewMorphisms(remove_morphisms));
-                }
-            }
-
-            category.action_do(undo_actions);
+            category.action_do(undo_actions, step);
 
             if self.check_goal() {
                 println!("Hooray! Goal reached!");
                 // TODO: display on screen
+                self.add_lemma();
+            }
+        }
+    }
+
+    /// Generalizes the solved goal into a new rule:
+    /// the things linked to the facts are given, and the rest is constructed.
+    fn add_lemma(&mut self) {
+        if self.lemma_added {
+            return;
+        }
+
+        let given = self.graph_link.bindings();
+        let goal = &self.goal_category.inner;
+
+        // Use the names from the goal, unless they are empty or ambiguous
+        let names = goal
+            .objects
+            .iter()
+            .map(|(_, object)| object.inner.label.clone())
+            .chain(
+                goal.morphisms
+                    .iter()
+                    .filter_map(|(_, morphism)| morphism.inner.label.clone()),
+            )
+            .fold(HashMap::<Label, usize>::new(), |mut names, name| {
+                *names.entry(name).or_default() += 1;
+                names
+            });
+        let label = |thing: CategoryThing| {
+            let (name, fallback) = match thing {
+                CategoryThing::Object { id } => (
+                    goal.objects
+                        .get(&id)
+                        .map(|object| object.inner.label.clone()),
+                    format!("o{}", id.raw()),
+                ),
+                CategoryThing::Morphism { id } => (
+                    goal.morphisms
+                        .get(&id)
+                        .and_then(|morphism| morphism.inner.label.clone()),
+                    format!("m{}", id.raw()),
+                ),
+            };
+            name.filter(|name| !name.is_empty() && names.get(name) == Some(&1))
+                .unwrap_or(fallback)
+        };
+        let is_given = |thing: CategoryThing| match thing {
+            CategoryThing::Object { .. } => given.get_object(&thing).is_some(),
+            CategoryThing::Morphism { .. } => given.get_morphism(&thing).is_some(),
+        };
+
+        match goal.to_lemma(is_given, label) {
+            Ok(rule) => {
+                let rule = RenderableRule::from_rule(&self.geng, &self.assets, rule.named("lemma"));
+                self.rules.push(rule);
+                self.lemma_added = true;
             }
+            Err(error) => warn!("Failed to turn the goal into a rule: {error:?}"),
         }
     }
 
@@ -111,6 +158,8 @@ impl GameState {
         self.fact_category
             .inner
             .find_candidates(&constraints, bindings)
+            .ok()
+            .flatten()
             .map(|mut candidates| candidates.next().is_some())
             .unwrap_or(false)
     }
@@ -56,9 +56,15 @@ pub struct Equality {
     pub color: Color<f32>,
 }
 
-pub fn object_name_from_tag_label(tag: &ObjectTag<&str>) -> Option<String> {
+pub fn object_name_from_tag_label(tag: &ObjectTag<&str, &str>) -> Option<String> {
     match &tag {
         ObjectTag::Product(a, b) => label_operation(a, b, "x"),
+        ObjectTag::Coproduct(a, b) => label_operation(a, b, "+"),
+        ObjectTag::Exponential(b, a) => label_operation(b, a, "^"),
+        ObjectTag::Equalizer(f, g) => label_function("Eq", &[f, g]),
+        ObjectTag::Coequalizer(f, g) => label_function("Coeq", &[f, g]),
+        ObjectTag::Pullback(f, g) => label_function("Pb", &[f, g]),
+        ObjectTag::Pushout(f, g) => label_function("Po", &[f, g]),
         _ => None,
     }
 }
@@ -75,6 +81,7 @@ pub fn infer_morphism_name(morphism: &Morphism, category: &Category) -> Option<S
 pub fn morphism_name_from_tag(tag: &MorphismTag, category: &Category) -> Option<String> {
     match tag {
         MorphismTag::Identity(_) => Some(format!("id")),
+        MorphismTag::Evaluation => Some(format!("eval")),
         MorphismTag::Isomorphism(_, _) => Some(format!("")),
         MorphismTag::Composition { first, second } => {
             let mut decomposed = category::util::decompose_morphism(*first, category);
@@ -124,3 +131,11 @@ fn label_operation(label_a: &str, label_b: &str, operation: &str) -> Option<Stri
 
     Some(format!("{}{}{}", first, operation, second))
 }
+
+fn label_function(name: &str, args: &[&str]) -> Option<String> {
+    if args.iter().any(|arg| arg.is_empty()) {
+        return None;
+    }
+
+    Some(format!("{}({})", name, args.join(", ")))
+}
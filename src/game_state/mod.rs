@@ -40,6 +40,7 @@ type Equalities = category::Equalities<Equality>;
 
 pub struct GameState {
     geng: Geng,
+    assets: Rc<Assets>,
     ui_camera: PixelPerfectCamera,
     state: State,
     rules: Vec<RenderableRule>,
@@ -50,6 +51,8 @@ pub struct GameState {
     dragging: Option<Dragging>,
     fact_selection: Option<RuleSelection>,
     goal_selection: Option<RuleSelection>,
+    /// Whether the solved goal has been turned into a rule.
+    lemma_added: bool,
 }
 
 impl GameState {
@@ -65,9 +68,11 @@ impl GameState {
             .collect();
         Self {
             geng: geng.clone(),
+            assets: assets.clone(),
             dragging: None,
             fact_selection: None,
             goal_selection: None,
+            lemma_added: false,
             focused_category: FocusedCategory::Fact,
             ui_camera: PixelPerfectCamera,
             graph_link: GraphLink::new(&fact_category.inner, &goal_category.inner),
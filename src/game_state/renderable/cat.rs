@@ -9,8 +9,11 @@ pub struct RenderableCategory {
     pub camera: BoundedCamera,
     pub texture: ugli::Texture,
     pub texture_size: Vec2<usize>,
+    /// Each entry has its step in the script.
     action_history: Vec<Vec<CategoryAction>>,
-    redo_history: Vec<Vec<CategoryAction>>,
+    redo_history: Vec<(Vec<CategoryAction>, Option<category::ScriptStep<Label>>)>,
+    /// The rules applied to the category, following the undo history.
+    pub script: category::ScriptRecorder<Label>,
     pub undo_button: Option<AABB<f32>>,
     pub redo_button: Option<AABB<f32>>,
     hide_morphisms: bool,
@@ -26,6 +29,7 @@ impl RenderableCategory {
             texture: ugli::Texture::new_with(geng.ugli(), texture_size, |_| Color::BLACK),
             action_history: vec![],
             redo_history: vec![],
+            script: category::ScriptRecorder::new(&initial_names(&category)),
             undo_button: if buttons { Some(AABB::ZERO) } else { None },
             redo_button: if buttons { Some(AABB::ZERO) } else { None },
             inner: category,
@@ -94,13 +98,15 @@ impl RenderableCategory {
         }
     }
 
-    pub fn action_do(&mut self, actions: Vec<CategoryAction>) {
+    /// Adds the actions of the rule application to the history, and its step to the script.
+    pub fn action_do(&mut self, actions: Vec<CategoryAction>, step: category::ScriptStep<Label>) {
         if actions.is_empty() {
             return;
         }
 
         self.redo_history.clear();
         self.action_history.push(actions);
+        self.script.push(step);
     }
 
     pub fn action_undo(&mut self) {
@@ -109,17 +115,55 @@ impl RenderableCategory {
             for action in actions {
                 redo_actions.extend(self.inner.action_do(action));
             }
-            self.redo_history.push(redo_actions);
+            let step = self.script.pop();
+            self.redo_history.push((redo_actions, step));
         }
     }
 
     pub fn action_redo(&mut self) {
-        if let Some(actions) = self.redo_history.pop() {
+        if let Some((actions, step)) = self.redo_history.pop() {
             let mut undo_actions = Vec::new();
             for action in actions {
                 undo_actions.extend(self.inner.action_do(action));
             }
             self.action_history.push(undo_actions);
+            if let Some(step) = step {
+                self.script.push(step);
+            }
         }
     }
 }
+
+/// Names the things in the category by their labels, unless they are empty or ambiguous,
+/// so that the script can refer to them.
+fn initial_names(category: &Category) -> Bindings {
+    let mut counts = HashMap::<&str, usize>::new();
+    for (_, object) in category.objects.iter() {
+        *counts.entry(&object.inner.label).or_default() += 1;
+    }
+    for (_, morphism) in category.morphisms.iter() {
+        if let Some(label) = &morphism.inner.label {
+            *counts.entry(label).or_default() += 1;
+        }
+    }
+    let is_unique = |label: &str| !label.is_empty() && counts.get(label) == Some(&1);
+
+    let mut names = Bindings::new();
+    for (&id, object) in category.objects.iter() {
+        let name = Some(&object.inner.label)
+            .filter(|label| is_unique(label))
+            .cloned()
+            .unwrap_or_else(|| format!("o{}", id.raw()));
+        names.bind_object(name, id);
+    }
+    for (&id, morphism) in category.morphisms.iter() {
+        let name = morphism
+            .inner
+            .label
+            .clone()
+            .filter(|label| is_unique(label))
+            .unwrap_or_else(|| format!("m{}", id.raw()));
+        names.bind_morphism(name, id);
+    }
+    names
+}
@@ -4,8 +4,6 @@ pub struct RenderableRule {
     pub inner: Rule,
     pub inverse: Vec<Rule>,
     pub category: RenderableCategory,
-    pub input: Vec<RuleInput<Label>>,
-    pub inverse_input: Vec<RuleInput<Label>>,
     pub bindings: Bindings,
 }
 
@@ -24,7 +22,7 @@ impl RenderableRule {
         fn object_constructor(
             part: category::RulePart,
             label: &Label,
-            _tags: &Vec<ObjectTag<Label>>,
+            _tags: &Vec<ObjectTag<Label, Label>>,
         ) -> Point {
             Point::new(label, part_color(part))
         }
@@ -51,7 +49,7 @@ impl RenderableRule {
             }
         }
 
-        let (category, input, bindings) = Category::from_rule(
+        let (category, _, bindings) = Category::from_rule(
             &rule,
             object_constructor,
             morphism_constructor,
@@ -60,25 +58,10 @@ impl RenderableRule {
 
         let inverse = rule.invert();
 
-        let inverse_input = inverse
-            .last()
-            .map(|rule| {
-                Category::from_rule(
-                    rule,
-                    object_constructor,
-                    morphism_constructor,
-                    equality_constructor,
-                )
-                .1
-            })
-            .unwrap_or_default();
-
         Self {
             category: RenderableCategory::new(geng, assets, category, false),
             inner: rule,
             inverse,
-            input,
-            inverse_input,
             bindings,
         }
     }
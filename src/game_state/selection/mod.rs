@@ -154,6 +154,10 @@ fn infer_construction(
 ) -> Vec<RuleInput<Label>> {
     category
         .find_candidates(all_constraints, bindings)
+        .unwrap_or_else(|error| {
+            warn!("Invalid selection: {error:?}");
+            None
+        })
         .map(|candidates| {
             candidates
                 .into_iter()